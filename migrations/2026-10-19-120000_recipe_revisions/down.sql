@@ -0,0 +1,3 @@
+DROP TABLE recipe_revision_tags;
+DROP TABLE recipe_revision_ingredients;
+DROP TABLE recipe_revisions;
@@ -0,0 +1,34 @@
+-- Snapshot of a recipe taken every time it is overwritten
+CREATE TABLE recipe_revisions (
+  id INTEGER NOT NULL PRIMARY KEY,
+  recipe_id INTEGER NOT NULL,
+  revision INTEGER NOT NULL, -- Counts up from 1 for each recipe
+  created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  name VARCHAR(255) NOT NULL,
+  instructions_markdown TEXT NOT NULL,
+  notes_markdown TEXT NOT NULL,
+
+  UNIQUE (recipe_id, revision),
+  FOREIGN KEY (recipe_id) REFERENCES recipes(id) ON DELETE CASCADE
+);
+
+CREATE TABLE recipe_revision_ingredients (
+  recipe_revision_id INTEGER NOT NULL,
+  ingredient_id INTEGER NOT NULL,
+  display_order INTEGER NOT NULL,
+  quantity VARCHAR(255) NOT NULL,
+  notes_markdown TEXT NOT NULL,
+
+  PRIMARY KEY (recipe_revision_id, ingredient_id),
+  FOREIGN KEY (recipe_revision_id) REFERENCES recipe_revisions(id) ON DELETE CASCADE,
+  FOREIGN KEY (ingredient_id) REFERENCES ingredients(id) ON DELETE RESTRICT
+);
+
+CREATE TABLE recipe_revision_tags (
+  recipe_revision_id INTEGER NOT NULL,
+  tag_id VARCHAR(255) NOT NULL,
+
+  PRIMARY KEY (recipe_revision_id, tag_id),
+  FOREIGN KEY (recipe_revision_id) REFERENCES recipe_revisions(id) ON DELETE CASCADE,
+  FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE
+);
@@ -0,0 +1,2 @@
+ALTER TABLE recipes
+  ADD COLUMN deleted_at TEXT NULL; -- Recipe is in the trash when not NULL
@@ -0,0 +1,10 @@
+-- Unsaved editor state, so nothing is lost if the editor is closed early
+CREATE TABLE recipe_drafts (
+  id INTEGER NOT NULL PRIMARY KEY,
+  recipe_id INTEGER NULL UNIQUE, -- NULL for new recipes
+  updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  name VARCHAR(255) NOT NULL,
+  editor_state TEXT NOT NULL, -- JSON
+
+  FOREIGN KEY (recipe_id) REFERENCES recipes(id) ON DELETE CASCADE
+);
@@ -0,0 +1,7 @@
+-- Other names for an ingredient, like "scallion" for "green onion"
+CREATE TABLE ingredient_aliases (
+  name VARCHAR(255) NOT NULL PRIMARY KEY COLLATE NOCASE,
+  ingredient_id INTEGER NOT NULL,
+
+  FOREIGN KEY (ingredient_id) REFERENCES ingredients(id) ON DELETE CASCADE
+);
@@ -0,0 +1,4 @@
+ALTER TABLE ingredients
+  DROP COLUMN category_id;
+
+DROP TABLE ingredient_categories;
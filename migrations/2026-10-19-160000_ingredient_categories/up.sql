@@ -0,0 +1,21 @@
+-- Store sections, listed in the order they are walked through
+CREATE TABLE ingredient_categories (
+  id VARCHAR(255) NOT NULL PRIMARY KEY,
+  name VARCHAR(255) NOT NULL,
+  aisle_order INTEGER NOT NULL
+);
+
+INSERT INTO ingredient_categories (id, name, aisle_order) VALUES
+  ('produce', 'Produce', 1),
+  ('bakery', 'Bakery', 2),
+  ('meat', 'Meat & Seafood', 3),
+  ('dairy', 'Dairy & Eggs', 4),
+  ('grains', 'Pasta, Rice & Grains', 5),
+  ('canned', 'Canned & Jarred', 6),
+  ('baking', 'Baking', 7),
+  ('spices', 'Spices & Seasonings', 8),
+  ('frozen', 'Frozen', 9);
+
+-- Not a foreign key so the column can be dropped again, deleting a category clears it instead
+ALTER TABLE ingredients
+  ADD COLUMN category_id VARCHAR(255) NULL;
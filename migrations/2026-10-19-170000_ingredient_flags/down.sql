@@ -0,0 +1,4 @@
+ALTER TABLE ingredients
+  DROP COLUMN flags_reviewed;
+
+DROP TABLE ingredient_flags;
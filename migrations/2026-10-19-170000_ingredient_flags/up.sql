@@ -0,0 +1,12 @@
+-- Allergens and dietary properties, like "gluten" or "meat"
+CREATE TABLE ingredient_flags (
+  ingredient_id INTEGER NOT NULL,
+  flag VARCHAR(255) NOT NULL,
+
+  PRIMARY KEY (ingredient_id, flag),
+  FOREIGN KEY (ingredient_id) REFERENCES ingredients(id) ON DELETE CASCADE
+);
+
+-- Ingredients without flags could either be unchecked or free of everything
+ALTER TABLE ingredients
+  ADD COLUMN flags_reviewed BOOLEAN NOT NULL DEFAULT 0;
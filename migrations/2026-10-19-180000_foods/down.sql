@@ -0,0 +1,3 @@
+DROP TABLE food_portions;
+DROP TABLE ingredient_foods;
+DROP TABLE foods;
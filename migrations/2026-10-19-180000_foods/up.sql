@@ -0,0 +1,39 @@
+-- Entries imported from a nutrient database like USDA FoodData Central
+CREATE TABLE foods (
+  id INTEGER NOT NULL PRIMARY KEY, -- ID from the source database, e.g. "fdc_id"
+  description TEXT NOT NULL,
+
+  -- Nutrients are per 100 grams
+  calories REAL NULL,
+  protein_grams REAL NULL,
+  fat_grams REAL NULL,
+  carbohydrate_grams REAL NULL,
+
+  -- Used to convert volumes and counts into grams
+  grams_per_ml REAL NULL,
+  grams_per_each REAL NULL
+);
+
+CREATE INDEX foods_description_index ON foods (description);
+
+CREATE TABLE ingredient_foods (
+  ingredient_id INTEGER NOT NULL PRIMARY KEY,
+  food_id INTEGER NOT NULL,
+
+  -- Overrides the conversions from the food
+  grams_per_ml REAL NULL,
+  grams_per_each REAL NULL,
+
+  FOREIGN KEY (ingredient_id) REFERENCES ingredients(id) ON DELETE CASCADE,
+  FOREIGN KEY (food_id) REFERENCES foods(id) ON DELETE CASCADE
+);
+
+-- Weight of one of a unit that depends on the food, like a can of beans or a clove of garlic
+CREATE TABLE food_portions (
+  food_id INTEGER NOT NULL,
+  unit TEXT NOT NULL, -- Singular unit name, e.g. "can"
+  grams REAL NOT NULL,
+
+  PRIMARY KEY (food_id, unit),
+  FOREIGN KEY (food_id) REFERENCES foods(id) ON DELETE CASCADE
+);
@@ -0,0 +1,13 @@
+-- Every price seen for an ingredient, so old prices are kept as history
+CREATE TABLE ingredient_prices (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  ingredient_id INTEGER NOT NULL,
+  price_cents INTEGER NOT NULL,
+  package_quantity TEXT NOT NULL, -- Size of the package, like "5 lb" or "12"
+  store TEXT NULL,
+  recorded_on TEXT NOT NULL DEFAULT CURRENT_DATE,
+
+  FOREIGN KEY (ingredient_id) REFERENCES ingredients(id) ON DELETE CASCADE
+);
+
+CREATE INDEX ingredient_prices_ingredient_index ON ingredient_prices (ingredient_id, recorded_on);
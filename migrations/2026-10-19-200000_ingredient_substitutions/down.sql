@@ -0,0 +1,2 @@
+DROP TABLE substitution_components;
+DROP TABLE ingredient_substitutions;
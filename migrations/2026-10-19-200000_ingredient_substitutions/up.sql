@@ -0,0 +1,21 @@
+-- Replace an amount of one ingredient with one or more others,
+--   like "1 cup buttermilk = 1 cup milk + 1 tbsp lemon juice"
+CREATE TABLE ingredient_substitutions (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  ingredient_id INTEGER NOT NULL,
+  quantity TEXT NOT NULL, -- Amount of the ingredient being replaced
+  notes_markdown TEXT NOT NULL DEFAULT '',
+
+  FOREIGN KEY (ingredient_id) REFERENCES ingredients(id) ON DELETE CASCADE
+);
+
+CREATE TABLE substitution_components (
+  substitution_id INTEGER NOT NULL,
+  ingredient_id INTEGER NOT NULL,
+  display_order INTEGER NOT NULL,
+  quantity TEXT NOT NULL,
+
+  PRIMARY KEY (substitution_id, ingredient_id),
+  FOREIGN KEY (substitution_id) REFERENCES ingredient_substitutions(id) ON DELETE CASCADE,
+  FOREIGN KEY (ingredient_id) REFERENCES ingredients(id) ON DELETE CASCADE
+);
@@ -0,0 +1,2 @@
+DROP TABLE recipe_step_ingredients;
+DROP TABLE recipe_steps;
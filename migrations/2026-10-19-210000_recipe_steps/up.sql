@@ -0,0 +1,22 @@
+-- Structured form of the instructions, read from the numbered list when the recipe is saved
+CREATE TABLE recipe_steps (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  recipe_id INTEGER NOT NULL,
+  step_number INTEGER NOT NULL,
+  text_markdown TEXT NOT NULL,
+  duration_seconds INTEGER NULL,
+  temperature TEXT NULL, -- Like "350°F"
+
+  UNIQUE (recipe_id, step_number),
+  FOREIGN KEY (recipe_id) REFERENCES recipes(id) ON DELETE CASCADE
+);
+
+-- Ingredients mentioned in each step
+CREATE TABLE recipe_step_ingredients (
+  step_id INTEGER NOT NULL,
+  ingredient_id INTEGER NOT NULL,
+
+  PRIMARY KEY (step_id, ingredient_id),
+  FOREIGN KEY (step_id) REFERENCES recipe_steps(id) ON DELETE CASCADE,
+  FOREIGN KEY (ingredient_id) REFERENCES ingredients(id) ON DELETE CASCADE
+);
@@ -0,0 +1,11 @@
+-- Typed links between recipes, like "Garlic Bread" is a side for "Lasagna"
+--   Reads as: recipe_id <kind> related_recipe_id
+CREATE TABLE recipe_relations (
+  recipe_id INTEGER NOT NULL,
+  related_recipe_id INTEGER NOT NULL,
+  kind TEXT NOT NULL, -- pairs-with, side-for, variation-of, or replaces
+
+  PRIMARY KEY (recipe_id, related_recipe_id, kind),
+  FOREIGN KEY (recipe_id) REFERENCES recipes(id) ON DELETE CASCADE,
+  FOREIGN KEY (related_recipe_id) REFERENCES recipes(id) ON DELETE CASCADE
+);
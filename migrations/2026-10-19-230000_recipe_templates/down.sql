@@ -0,0 +1,2 @@
+DROP TABLE recipe_template_tags;
+DROP TABLE recipe_templates;
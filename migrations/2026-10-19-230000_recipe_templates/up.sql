@@ -0,0 +1,18 @@
+-- Starting points for new recipes, like "bread" or "slow cooker"
+CREATE TABLE recipe_templates (
+  id VARCHAR(255) NOT NULL PRIMARY KEY,
+  name VARCHAR(255) NOT NULL,
+  ingredients_text TEXT NOT NULL, -- Same format as the editor, matched to ingredients when a recipe is created
+  instructions_markdown TEXT NOT NULL,
+  notes_markdown TEXT NOT NULL
+);
+
+-- Tags applied to new recipes made from the template
+CREATE TABLE recipe_template_tags (
+  template_id VARCHAR(255) NOT NULL,
+  tag_id VARCHAR(255) NOT NULL,
+
+  PRIMARY KEY (template_id, tag_id),
+  FOREIGN KEY (template_id) REFERENCES recipe_templates(id) ON DELETE CASCADE,
+  FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE
+);
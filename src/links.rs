@@ -0,0 +1,312 @@
+use convert_case::{Case, Casing};
+use diesel::prelude::*;
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+
+use crate::models::Recipe;
+
+/// Link to another recipe written as `[[Recipe Name]]` or `[[#42]]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecipeLink<'s> {
+  pub start: usize,  // Byte offset in the markdown
+  pub text: &'s str, // Including the brackets
+  pub target: LinkTarget<'s>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LinkTarget<'s> {
+  Id(i32),
+  Name(&'s str),
+}
+
+/// How linked recipes are written when a recipe is turned into markdown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkStyle {
+  /// Name and ID, since the terminal can't follow links
+  Terminal,
+
+  /// Relative link to the markdown file from `dump`
+  Markdown,
+
+  /// Relative link to the page written next to the `print --web` page
+  Html,
+}
+
+/// Recipes by ID, name, and slug, along with the recipes linking to each one
+///
+/// Built once per command, so that rendering many recipes doesn't search every recipe for every link.
+pub struct LinkIndex {
+  recipes: Vec<Recipe>,
+  by_id: HashMap<i32, usize>,
+  by_name: HashMap<String, Vec<usize>>, // Lowercase name
+  by_slug: HashMap<String, Vec<usize>>,
+  backlinks: HashMap<i32, Vec<usize>>, // Recipes with working links to the recipe with this ID
+  style: LinkStyle,
+  linkable_ids: Option<HashSet<i32>>,
+}
+
+/// Every recipe link in the markdown, in order
+pub fn find_links(markdown: &str) -> Vec<RecipeLink<'_>> {
+  let mut links = Vec::new();
+  let mut rest = markdown;
+  let mut offset = 0;
+
+  while let Some(start) = rest.find("[[") {
+    let after_open = &rest[start + 2..];
+    let Some(length) = after_open.find("]]") else {
+      break;
+    };
+
+    // "[[a [[b]]" links to "b", and links can't span lines
+    let inner = &after_open[..length];
+    if inner.contains("[[") || inner.contains('\n') || inner.trim().is_empty() {
+      let skip = start + 2 + inner.find("[[").unwrap_or(length);
+      offset += skip;
+      rest = &rest[skip..];
+      continue;
+    }
+
+    let end = start + 2 + length + 2;
+    let inner = inner.trim();
+    let target = match inner.strip_prefix('#').map(|id| id.trim().parse()) {
+      Some(Ok(id)) => LinkTarget::Id(id),
+      _ => LinkTarget::Name(inner),
+    };
+    links.push(RecipeLink {
+      start: offset + start,
+      text: &markdown[offset + start..offset + end],
+      target,
+    });
+
+    offset += end;
+    rest = &rest[end..];
+  }
+
+  links
+}
+
+/// Replace each recipe link with the result of the closure, or leave it as is when it returns None
+pub fn replace_links(markdown: &str, mut replace: impl FnMut(&RecipeLink) -> Option<String>) -> String {
+  let mut result = String::with_capacity(markdown.len());
+  let mut copied_up_to = 0;
+
+  for link in find_links(markdown) {
+    if let Some(replacement) = replace(&link) {
+      result.push_str(&markdown[copied_up_to..link.start]);
+      result.push_str(&replacement);
+      copied_up_to = link.start + link.text.len();
+    }
+  }
+
+  result.push_str(&markdown[copied_up_to..]);
+  result
+}
+
+impl LinkIndex {
+  /// Index of every recipe outside the trash
+  pub fn load(style: LinkStyle, conn: &mut SqliteConnection) -> QueryResult<Self> {
+    Ok(Self::new(Recipe::all_ordered(conn)?, style))
+  }
+
+  pub fn new(recipes: Vec<Recipe>, style: LinkStyle) -> Self {
+    let mut index = Self {
+      by_id: HashMap::new(),
+      by_name: HashMap::new(),
+      by_slug: HashMap::new(),
+      backlinks: HashMap::new(),
+      recipes: Vec::new(),
+      style,
+      linkable_ids: None,
+    };
+    for (position, recipe) in recipes.iter().enumerate() {
+      index.by_id.insert(recipe.id, position);
+      index
+        .by_name
+        .entry(recipe.name.to_lowercase())
+        .or_default()
+        .push(position);
+      index.by_slug.entry(recipe.get_slug()).or_default().push(position);
+    }
+    index.recipes = recipes;
+
+    let mut backlinks: HashMap<i32, Vec<usize>> = HashMap::new();
+    for (position, recipe) in index.recipes.iter().enumerate() {
+      for linked in index
+        .linked_recipes(recipe)
+        .into_iter()
+        .filter(|linked| linked.id != recipe.id)
+      {
+        backlinks.entry(linked.id).or_default().push(position);
+      }
+    }
+    Self { backlinks, ..index }
+  }
+
+  /// Only link to these recipes, and write any others as plain names
+  pub fn with_linkable_ids(self, ids: HashSet<i32>) -> Self {
+    Self {
+      linkable_ids: Some(ids),
+      ..self
+    }
+  }
+
+  /// Recipes a link could refer to, where anything other than exactly one match is a broken link
+  pub fn find(&self, target: &LinkTarget) -> Vec<&Recipe> {
+    let positions = match target {
+      LinkTarget::Id(id) => self.by_id.get(id).into_iter().copied().collect_vec(),
+      LinkTarget::Name(name) => {
+        let by_name = self.by_name.get(&name.to_lowercase()).into_iter().flatten();
+        let by_slug = self.by_slug.get(&name.to_case(Case::Kebab)).into_iter().flatten();
+        by_name.chain(by_slug).copied().sorted().dedup().collect_vec()
+      },
+    };
+    positions.into_iter().map(|position| &self.recipes[position]).collect()
+  }
+
+  /// Recipes that the recipe has working links to
+  pub fn linked_recipes(&self, recipe: &Recipe) -> Vec<&Recipe> {
+    recipe
+      .get_links()
+      .iter()
+      .filter_map(|link| match self.find(&link.target).as_slice() {
+        [linked] => Some(*linked),
+        _ => None,
+      })
+      .unique_by(|linked| linked.id)
+      .collect()
+  }
+
+  /// Recipes with working links to the recipe, in the order they were loaded
+  pub fn backlinks(&self, recipe_id: i32) -> Vec<&Recipe> {
+    self
+      .backlinks
+      .get(&recipe_id)
+      .into_iter()
+      .flatten()
+      .map(|position| &self.recipes[*position])
+      .collect()
+  }
+
+  pub fn link_markdown(&self, recipe: &Recipe) -> String {
+    match &self.linkable_ids {
+      Some(ids) if !ids.contains(&recipe.id) => recipe.name.clone(),
+      _ => recipe.link_markdown(self.style),
+    }
+  }
+
+  /// Replace every working link in the markdown, and leave broken links as written so they stand out
+  pub fn resolve_links(&self, markdown: &str) -> String {
+    replace_links(markdown, |link| match self.find(&link.target).as_slice() {
+      [recipe] => Some(self.link_markdown(recipe)),
+      _ => None,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn targets(markdown: &str) -> Vec<LinkTarget<'_>> {
+    find_links(markdown).into_iter().map(|link| link.target).collect()
+  }
+
+  #[test]
+  fn links_by_name_and_id() {
+    assert_eq!(
+      targets("Serve with [[Garlic Bread]] or [[#42]]."),
+      vec![LinkTarget::Name("Garlic Bread"), LinkTarget::Id(42)]
+    );
+    assert_eq!(targets("[[ Garlic Bread ]]"), vec![LinkTarget::Name("Garlic Bread")]);
+    assert_eq!(targets("[[#abc]]"), vec![LinkTarget::Name("#abc")]);
+  }
+
+  #[test]
+  fn nested_links_use_the_innermost() {
+    let links = find_links("[[a [[b]] c]]");
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].target, LinkTarget::Name("b"));
+    assert_eq!(links[0].text, "[[b]]");
+    assert_eq!(links[0].start, 4);
+  }
+
+  #[test]
+  fn broken_syntax_is_not_a_link() {
+    assert_eq!(targets("[[Garlic Bread"), vec![]);
+    assert_eq!(targets("[[Garlic\nBread]]"), vec![]);
+    assert_eq!(targets("[[  ]] and [[]]"), vec![]);
+    assert_eq!(targets("[Garlic Bread]"), vec![]);
+    assert_eq!(targets("[[Unclosed and [[Closed]]"), vec![LinkTarget::Name("Closed")]);
+  }
+
+  #[test]
+  fn multibyte_text() {
+    let markdown = "Crème [[Brûlée]] with [[Café Crème]] ☕";
+    let links = find_links(markdown);
+    assert_eq!(links.len(), 2);
+    assert_eq!(
+      &markdown[links[0].start..links[0].start + links[0].text.len()],
+      "[[Brûlée]]"
+    );
+    assert_eq!(links[1].target, LinkTarget::Name("Café Crème"));
+
+    let replaced = replace_links(markdown, |link| match link.target {
+      LinkTarget::Name(name) => Some(name.to_uppercase()),
+      LinkTarget::Id(_) => None,
+    });
+    assert_eq!(replaced, "Crème BRÛLÉE with CAFÉ CRÈME ☕");
+  }
+
+  #[test]
+  fn replace_leaves_unresolved_links() {
+    let replaced = replace_links("[[#1]], [[#2]], and [[#3]]", |link| match link.target {
+      LinkTarget::Id(2) => None,
+      LinkTarget::Id(id) => Some(format!("Recipe {}", id)),
+      LinkTarget::Name(_) => None,
+    });
+    assert_eq!(replaced, "Recipe 1, [[#2]], and Recipe 3");
+    assert_eq!(replace_links("No links", |_| Some("x".into())), "No links");
+  }
+
+  fn recipe(id: i32, name: &str, instructions_markdown: &str) -> Recipe {
+    Recipe {
+      id,
+      name: name.into(),
+      instructions_markdown: instructions_markdown.into(),
+      notes_markdown: String::new(),
+      deleted_at: None,
+    }
+  }
+
+  #[test]
+  fn index_resolves_links_and_backlinks() {
+    let links = LinkIndex::new(
+      vec![
+        recipe(1, "Garlic Bread", "Serve with [[lasagna]]"),
+        recipe(2, "Lasagna", "Serve with [[garlic-bread]] and [[Pie]]"),
+        recipe(3, "Pie", "See [[#3]]"),
+        recipe(4, "Pie", ""),
+      ],
+      LinkStyle::Terminal,
+    );
+
+    assert_eq!(links.find(&LinkTarget::Name("LASAGNA")).len(), 1);
+    assert_eq!(links.find(&LinkTarget::Name("Pie")).len(), 2);
+    assert_eq!(links.find(&LinkTarget::Id(5)).len(), 0);
+
+    let backlinks = |id| links.backlinks(id).iter().map(|r| r.id).collect::<Vec<_>>();
+    assert_eq!(backlinks(1), vec![2]);
+    assert_eq!(backlinks(2), vec![1]);
+    assert_eq!(backlinks(3), Vec::<i32>::new()); // Ambiguous links and links to itself don't count
+
+    assert_eq!(
+      links.resolve_links("[[Garlic Bread]] and [[Pie]]"),
+      "Garlic Bread (#1) and [[Pie]]"
+    );
+    let links = links.with_linkable_ids(HashSet::from([2]));
+    assert_eq!(
+      links.resolve_links("[[#1]] and [[#2]]"),
+      "Garlic Bread and Lasagna (#2)"
+    );
+  }
+}
@@ -1,11 +1,17 @@
+mod links;
 mod models;
+mod name_match;
+mod quantity;
+mod schedule;
 mod schema;
+mod steps;
 mod subcommand;
 mod ui;
 
 use clap::Parser;
 use diesel::{connection::SimpleConnection, Connection, SqliteConnection};
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+use std::process;
 
 const MIGRATIONS: EmbeddedMigrations = embed_migrations!();
 
@@ -20,9 +26,16 @@ struct Opt {
   subcommand: subcommand::OptSubcommand,
 }
 
-fn main() -> subcommand::Result<()> {
+fn main() {
   let opt: Opt = Opt::parse();
 
+  if let Err(e) = run(opt) {
+    eprintln!("Error: {}", e);
+    process::exit(1);
+  }
+}
+
+fn run(opt: Opt) -> subcommand::Result<()> {
   // Create the database file if it doesn't exist
   let mut conn = SqliteConnection::establish(&opt.database_file)?;
   conn.batch_execute("PRAGMA foreign_keys = ON;")?;
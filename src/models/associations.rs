@@ -5,7 +5,39 @@
 macro_rules! model_base(
   () => {
     model_base!(no update);
+    model_base!(@ update);
+  };
+
+  (order by $order:expr) => {
+    model_base!();
+    paste::item! {
+      model_base!(@ order by $crate::schema::$order);
+    }
+  };
+
+  // Rows that don't match the filter are hidden from "all" queries
+  (order by $order:expr, where $filter:expr) => {
+    model_base!(@ common);
+    model_base!(@ update);
+    paste::item! {
+      model_base!(@ all, where $crate::schema::$filter);
+      model_base!(@ order by $crate::schema::$order, where $crate::schema::$filter);
+    }
+  };
 
+  (no update, order by $order:expr) => {
+    model_base!(no update);
+    paste::item! {
+      model_base!(@ order by $crate::schema::$order);
+    }
+  };
+
+  (no update) => {
+    model_base!(@ common);
+    model_base!(@ all);
+  };
+
+  (@ update) => {
     // Save changes to database
     pub fn update(
       &self,
@@ -34,21 +66,7 @@ macro_rules! model_base(
     }
   };
 
-  (order by $order:expr) => {
-    model_base!();
-    paste::item! {
-      model_base!(@ order by $crate::schema::$order);
-    }
-  };
-
-  (no update, order by $order:expr) => {
-    model_base!(no update);
-    paste::item! {
-      model_base!(@ order by $crate::schema::$order);
-    }
-  };
-
-  (no update) => {
+  (@ all $(, where $filter:expr)?) => {
     // Get all
     pub fn all(
       conn: &mut diesel::SqliteConnection,
@@ -56,6 +74,7 @@ macro_rules! model_base(
       use diesel::prelude::*;
 
       <Self as diesel::associations::HasTable>::table()
+        $(.filter($filter))?
         .get_results::<Self>(conn)
     }
 
@@ -66,9 +85,12 @@ macro_rules! model_base(
       use diesel::prelude::*;
 
       <Self as diesel::associations::HasTable>::table()
+        $(.filter($filter))?
         .count().get_result::<i64>(conn)
     }
+  };
 
+  (@ common) => {
     // Find from ID
     pub fn find(
       id: <&Self as diesel::associations::Identifiable>::Id,
@@ -202,7 +224,7 @@ macro_rules! model_base(
     }
   };
 
-  (@ order by $order:expr) => {
+  (@ order by $order:expr $(, where $filter:expr)?) => {
 
     // Get all ordered by
     pub fn all_ordered(
@@ -210,7 +232,9 @@ macro_rules! model_base(
     ) -> diesel::prelude::QueryResult<Vec<Self>> {
         use diesel::prelude::*;
 
-        <Self as diesel::associations::HasTable>::table().order_by($order)
+        <Self as diesel::associations::HasTable>::table()
+          $(.filter($filter))?
+          .order_by($order)
           .get_results::<Self>(conn)
     }
   };
@@ -632,6 +656,13 @@ macro_rules! has_many(
     }
   );
 
+  // Children that don't match the filter are hidden from "get" and "count" queries
+  ($child:ident through $through:ident, order by $order:expr, where $filter:expr) => (
+    paste::item! {
+      has_many!($child through $through, order by $order, where $filter, [<$child:snake s>]);
+    }
+  );
+
   ($child:ident through $through:ident, $func_base:ident) => {
     has_many!(@ $crate::models::$child => $crate::models::$through, $func_base);
   };
@@ -643,7 +674,19 @@ macro_rules! has_many(
     }
   };
 
-  (@ $child:path => $through:path, $func_base:ident) => {
+  ($child:ident through $through:ident, order by $order:expr, where $filter:expr, $func_base:ident) => {
+    paste::item! {
+      has_many!(@ $crate::models::$child => $crate::models::$through, $func_base, where $crate::schema::$filter);
+      has_many!(
+        @ $crate::models::$child => $crate::models::$through,
+        order by $crate::schema::$order,
+        $func_base,
+        where $crate::schema::$filter
+      );
+    }
+  };
+
+  (@ $child:path => $through:path, $func_base:ident $(, where $filter:expr)?) => {
 
     // Get children
     paste::item! {
@@ -659,6 +702,7 @@ macro_rules! has_many(
             <$through as diesel::associations::BelongsTo<Self>>::foreign_key_column()
               .eq(<&Self as diesel::associations::Identifiable>::id(&self)),
           )
+          $(.filter($filter))?
           .get_results::<($through, $child)>(conn)
           .map(|results| results.into_iter().map(|(_, second)| second).collect())
       }
@@ -678,6 +722,7 @@ macro_rules! has_many(
             <$through as diesel::associations::BelongsTo<Self>>::foreign_key_column()
               .eq(id),
           )
+          $(.filter($filter))?
           .get_results::<($through, $child)>(conn)
           .map(|results| results.into_iter().map(|(_, second)| second).collect())
       }
@@ -697,6 +742,7 @@ macro_rules! has_many(
             <$through as diesel::associations::BelongsTo<Self>>::foreign_key_column()
               .eq(<&Self as diesel::associations::Identifiable>::id(&self)),
           )
+          $(.filter($filter))?
           .count().get_result::<i64>(conn)
       }
     }
@@ -715,6 +761,7 @@ macro_rules! has_many(
             <$through as diesel::associations::BelongsTo<Self>>::foreign_key_column()
               .eq(id),
           )
+          $(.filter($filter))?
           .count().get_result::<i64>(conn)
       }
     }
@@ -806,12 +853,12 @@ macro_rules! has_many(
           .map(|item| <$through as $crate::models::ManyToManyConstructor<Self, $child>>::new(id, item))
           .collect();
 
-        Ok($through::insert_list(&new_entries, conn)?)
+        $through::insert_list(&new_entries, conn)
       }
     }
   };
 
-  (@ $child:path => $through:path, order by $order:expr, $func_base:ident) => {
+  (@ $child:path => $through:path, order by $order:expr, $func_base:ident $(, where $filter:expr)?) => {
     // Get children ordered
     paste::item! {
       pub fn [<get_ $func_base _ordered>](
@@ -826,6 +873,7 @@ macro_rules! has_many(
             <$through as diesel::associations::BelongsTo<Self>>::foreign_key_column()
               .eq(<&Self as diesel::associations::Identifiable>::id(&self)),
           )
+          $(.filter($filter))?
           .order_by($order)
           .get_results::<($through, $child)>(conn)
           .map(|results| results.into_iter().map(|(_, second)| second).collect())
@@ -846,6 +894,7 @@ macro_rules! has_many(
             <$through as diesel::associations::BelongsTo<Self>>::foreign_key_column()
               .eq(id),
           )
+          $(.filter($filter))?
           .order_by($order)
           .get_results::<($through, $child)>(conn)
           .map(|results| results.into_iter().map(|(_, second)| second).collect())
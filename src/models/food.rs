@@ -0,0 +1,108 @@
+use diesel::prelude::*;
+use std::ops::{Add, Mul};
+
+use crate::schema::foods;
+
+/// Entry imported from a nutrient database, with nutrients per 100 grams
+#[derive(Debug, Clone, Queryable, Insertable, Identifiable, AsChangeset)]
+#[diesel(treat_none_as_null = true)]
+pub struct Food {
+  pub id: i32,
+  pub description: String,
+  pub calories: Option<f64>,
+  pub protein_grams: Option<f64>,
+  pub fat_grams: Option<f64>,
+  pub carbohydrate_grams: Option<f64>,
+  pub grams_per_ml: Option<f64>,
+  pub grams_per_each: Option<f64>,
+}
+
+/// Calories and macronutrients, where grams are rounded only when displayed
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NutritionFacts {
+  pub calories: f64,
+  pub protein_grams: f64,
+  pub fat_grams: f64,
+  pub carbohydrate_grams: f64,
+}
+
+/// Nutrition of a whole recipe, along with any ingredients that couldn't be counted
+#[derive(Debug, Clone, Default)]
+pub struct NutritionReport {
+  pub total: NutritionFacts,
+  pub gaps: Vec<IngredientGap>,
+}
+
+/// Ingredient that was left out of an estimate, and why
+#[derive(Debug, Clone)]
+pub struct IngredientGap {
+  pub ingredient: String,
+  pub reason: String,
+}
+
+impl Food {
+  model_base!(order by foods::description.asc());
+  has_many!(FoodPortion);
+
+  /// Nutrients in the given weight of this food, treating missing values as zero
+  pub fn facts_for_grams(&self, grams: f64) -> NutritionFacts {
+    NutritionFacts {
+      calories: self.calories.unwrap_or(0.0),
+      protein_grams: self.protein_grams.unwrap_or(0.0),
+      fat_grams: self.fat_grams.unwrap_or(0.0),
+      carbohydrate_grams: self.carbohydrate_grams.unwrap_or(0.0),
+    } * (grams / 100.0)
+  }
+}
+
+impl NutritionFacts {
+  /// Label and formatted amount of each nutrient, in the order of a nutrition label
+  pub fn rows(&self) -> [(&'static str, String); 4] {
+    [
+      ("Calories", format!("{:.0}", self.calories)),
+      ("Fat", format!("{:.1} g", self.fat_grams)),
+      ("Carbohydrates", format!("{:.1} g", self.carbohydrate_grams)),
+      ("Protein", format!("{:.1} g", self.protein_grams)),
+    ]
+  }
+}
+
+impl Add for NutritionFacts {
+  type Output = Self;
+
+  fn add(self, other: Self) -> Self {
+    Self {
+      calories: self.calories + other.calories,
+      protein_grams: self.protein_grams + other.protein_grams,
+      fat_grams: self.fat_grams + other.fat_grams,
+      carbohydrate_grams: self.carbohydrate_grams + other.carbohydrate_grams,
+    }
+  }
+}
+
+impl Mul<f64> for NutritionFacts {
+  type Output = Self;
+
+  fn mul(self, factor: f64) -> Self {
+    Self {
+      calories: self.calories * factor,
+      protein_grams: self.protein_grams * factor,
+      fat_grams: self.fat_grams * factor,
+      carbohydrate_grams: self.carbohydrate_grams * factor,
+    }
+  }
+}
+
+impl NutritionReport {
+  pub fn add_gap(&mut self, ingredient: &str, reason: &str) {
+    self.gaps.push(IngredientGap {
+      ingredient: ingredient.into(),
+      reason: reason.into(),
+    });
+  }
+
+  /// True if nothing at all could be counted
+  pub fn is_empty(&self) -> bool {
+    self.total == NutritionFacts::default()
+  }
+}
@@ -0,0 +1,24 @@
+use diesel::prelude::*;
+
+use crate::models::Food;
+use crate::quantity::Unit;
+use crate::schema::food_portions;
+
+/// Weight of one can, slice, clove, etc. of a food
+#[derive(Debug, Clone, Queryable, Insertable, Identifiable, AsChangeset, Associations)]
+#[diesel(primary_key(food_id, unit))]
+#[diesel(treat_none_as_null = true)]
+#[diesel(belongs_to(Food))]
+pub struct FoodPortion {
+  pub food_id: i32,
+  pub unit: String,
+  pub grams: f64,
+}
+
+impl FoodPortion {
+  model_base!();
+
+  pub fn get_unit(&self) -> Option<Unit> {
+    Unit::from_word(&self.unit)
+  }
+}
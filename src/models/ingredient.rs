@@ -1,35 +1,121 @@
 use diesel::prelude::*;
 use gtmpl_derive::Gtmpl;
+use itertools::Itertools;
 
-use super::{lower, RecipeIngredient};
-use crate::schema::ingredients;
+use super::{DietaryFlag, IngredientAlias, IngredientCategory, IngredientFlag, IngredientFood, RecipeIngredient};
+use crate::name_match::{similarity, MIN_SUGGEST_SCORE};
+use crate::quantity::Conversions;
+use crate::schema::{ingredient_flags, ingredients};
 
-#[derive(Debug, Clone, Queryable, Insertable, Identifiable, AsChangeset, Gtmpl)]
+#[derive(Debug, Clone, Queryable, Insertable, Identifiable, Associations, AsChangeset, Gtmpl)]
 #[diesel(treat_none_as_null = true)]
+#[diesel(belongs_to(IngredientCategory, foreign_key = category_id))]
 pub struct Ingredient {
   pub id: i32,
   pub name: String,
+  pub category_id: Option<String>,
+  pub flags_reviewed: bool,
 }
 
 #[derive(Debug, Clone, Insertable)]
 #[diesel(table_name = ingredients)]
 pub struct IngredientConstructor<'s> {
   pub name: &'s str,
+  pub category_id: Option<&'s str>,
 }
 
 impl Ingredient {
   model_base!(order by ingredients::name.asc());
 
-  // Get all ingredients that match any words of the name
-  pub fn find_by_name(input: &str, conn: &mut SqliteConnection) -> QueryResult<Vec<Self>> {
-    use crate::schema::ingredients::dsl::{ingredients, name};
+  belongs_to!(IngredientCategory, category);
+  has_many!(IngredientAlias, order by ingredient_aliases::name.asc(), ingredient_aliases);
+  has_many!(IngredientFlag);
+  has_many!(IngredientPrice, order by ingredient_prices::recorded_on.desc());
+  has_many!(IngredientSubstitution, order by ingredient_substitutions::id.asc());
 
-    let mut query = ingredients.into_boxed();
-    for word in input.split_whitespace() {
-      query = query.or_filter(lower(name).like(format!("%{}%", word)));
+  /// Weight conversions from the linked food, if any
+  pub fn get_conversions(&self, conn: &mut SqliteConnection) -> QueryResult<Conversions> {
+    match IngredientFood::find_optional(&self.id, conn)? {
+      Some(link) => link.conversions(&link.get_food(conn)?, conn),
+      None => Ok(Conversions::default()),
     }
+  }
+
+  pub fn get_dietary_flags(&self, conn: &mut SqliteConnection) -> QueryResult<Vec<DietaryFlag>> {
+    Ok(
+      self
+        .get_ingredient_flags(conn)?
+        .iter()
+        .filter_map(|f| DietaryFlag::from_id(&f.flag))
+        .sorted()
+        .collect(),
+    )
+  }
+
+  /// Replace all flags, marking the ingredient as reviewed
+  pub fn set_dietary_flags(&self, flags: &[DietaryFlag], conn: &mut SqliteConnection) -> QueryResult<Self> {
+    conn.transaction(|conn| {
+      diesel::delete(ingredient_flags::table.filter(ingredient_flags::ingredient_id.eq(self.id))).execute(conn)?;
+      IngredientFlag::insert_list(
+        &flags
+          .iter()
+          .unique()
+          .map(|flag| IngredientFlag {
+            ingredient_id: self.id,
+            flag: flag.id(),
+          })
+          .collect(),
+        conn,
+      )?;
+
+      Ingredient {
+        flags_reviewed: true,
+        ..self.clone()
+      }
+      .update(conn)
+    })
+  }
+
+  /// Look up an ingredient by ID, name, or alias, ignoring case
+  pub fn find_by_reference(reference: &str, conn: &mut SqliteConnection) -> QueryResult<Option<Self>> {
+    if let Ok(id) = reference.parse::<i32>() {
+      if let Some(ingredient) = Self::find_optional(&id, conn)? {
+        return Ok(Some(ingredient));
+      }
+    }
+
+    let by_name = Self::all(conn)?
+      .into_iter()
+      .find(|i| i.name.eq_ignore_ascii_case(reference.trim()));
+    if by_name.is_some() {
+      return Ok(by_name);
+    }
+
+    match IngredientAlias::find_optional(&reference.trim().to_string(), conn)? {
+      Some(alias) => alias.get_ingredient(conn).optional(),
+      None => Ok(None),
+    }
+  }
+
+  /// Ingredients with a name or alias similar to the input, best match first
+  pub fn find_similar(input: &str, conn: &mut SqliteConnection) -> QueryResult<Vec<(Self, f64)>> {
+    let all_aliases = IngredientAlias::all(conn)?;
+
+    let mut matches: Vec<_> = Self::all(conn)?
+      .into_iter()
+      .map(|ingredient| {
+        let score = all_aliases
+          .iter()
+          .filter(|alias| alias.ingredient_id == ingredient.id)
+          .map(|alias| similarity(input, &alias.name))
+          .fold(similarity(input, &ingredient.name), f64::max);
+        (ingredient, score)
+      })
+      .filter(|(_, score)| *score >= MIN_SUGGEST_SCORE)
+      .collect();
 
-    query.get_results(conn)
+    matches.sort_by(|(a, a_score), (b, b_score)| b_score.total_cmp(a_score).then_with(|| a.name.cmp(&b.name)));
+    Ok(matches)
   }
 
   pub fn markdown_string(&self, recipe_ingredient: &RecipeIngredient) -> String {
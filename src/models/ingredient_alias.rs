@@ -0,0 +1,20 @@
+use diesel::prelude::*;
+use gtmpl_derive::Gtmpl;
+
+use crate::models::Ingredient;
+use crate::schema::ingredient_aliases;
+
+#[derive(Debug, Clone, Queryable, Insertable, Identifiable, Associations, Gtmpl)]
+#[diesel(primary_key(name))]
+#[diesel(table_name = ingredient_aliases)]
+#[diesel(treat_none_as_null = true)]
+#[diesel(belongs_to(Ingredient))]
+pub struct IngredientAlias {
+  pub name: String,
+  pub ingredient_id: i32,
+}
+
+impl IngredientAlias {
+  model_base!(no update, order by ingredient_aliases::name.asc());
+  belongs_to!(Ingredient);
+}
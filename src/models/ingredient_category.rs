@@ -0,0 +1,37 @@
+use diesel::prelude::*;
+use gtmpl_derive::Gtmpl;
+
+use crate::schema::{ingredient_categories, ingredients};
+
+#[derive(Debug, Clone, Queryable, Insertable, Identifiable, AsChangeset, Gtmpl)]
+#[diesel(table_name = ingredient_categories)]
+#[diesel(treat_none_as_null = true)]
+pub struct IngredientCategory {
+  pub id: String,
+  pub name: String,
+  pub aisle_order: i32,
+}
+
+impl IngredientCategory {
+  model_base!(order by ingredient_categories::aisle_order.asc());
+  has_many!(Ingredient, order by ingredients::name.asc());
+
+  /// Aisle order that puts a new category at the end of the store
+  pub fn next_aisle_order(conn: &mut SqliteConnection) -> QueryResult<i32> {
+    let last_order: Option<i32> = ingredient_categories::table
+      .select(diesel::dsl::max(ingredient_categories::aisle_order))
+      .get_result(conn)?;
+    Ok(last_order.unwrap_or(0) + 1)
+  }
+
+  /// Delete the category, leaving its ingredients uncategorized
+  pub fn delete_and_clear(&self, conn: &mut SqliteConnection) -> QueryResult<()> {
+    conn.transaction(|conn| {
+      diesel::update(ingredients::table.filter(ingredients::category_id.eq(&self.id)))
+        .set(ingredients::category_id.eq(None::<String>))
+        .execute(conn)?;
+      self.delete(conn)?;
+      Ok(())
+    })
+  }
+}
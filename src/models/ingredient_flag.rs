@@ -0,0 +1,184 @@
+use clap::ValueEnum;
+use diesel::prelude::*;
+use gtmpl_derive::Gtmpl;
+use itertools::Itertools;
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::models::Ingredient;
+use crate::schema::ingredient_flags;
+
+#[derive(Debug, Clone, Queryable, Insertable, Identifiable, Associations, Gtmpl)]
+#[diesel(primary_key(ingredient_id, flag))]
+#[diesel(treat_none_as_null = true)]
+#[diesel(belongs_to(Ingredient))]
+pub struct IngredientFlag {
+  pub ingredient_id: i32,
+  pub flag: String,
+}
+
+/// Allergen or dietary property of an ingredient
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, ValueEnum)]
+pub enum DietaryFlag {
+  Gluten,
+  Dairy,
+  Egg,
+  Nuts,
+  Peanuts,
+  Soy,
+  Fish,
+  Shellfish,
+  Meat,
+  AnimalProduct,
+}
+
+/// Diet that a recipe can be classified as
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Diet {
+  Vegan,
+  Vegetarian,
+  Pescatarian,
+  GlutenFree,
+  DairyFree,
+  NutFree,
+}
+
+/// Allergens and diets of a recipe, rolled up from its ingredients
+#[derive(Debug, Clone, Default)]
+pub struct DietaryInfo {
+  pub contains: BTreeSet<DietaryFlag>,
+  pub unreviewed_ingredients: Vec<String>, // Can't be sure what these contain
+}
+
+impl IngredientFlag {
+  model_base!(no update);
+  belongs_to!(Ingredient);
+}
+
+impl DietaryFlag {
+  pub fn id(&self) -> String {
+    self.to_possible_value().unwrap().get_name().to_string() // Will not panic, no variants are skipped
+  }
+
+  pub fn from_id(id: &str) -> Option<Self> {
+    <Self as ValueEnum>::from_str(id, true).ok()
+  }
+
+  /// Other flags that are always true if this one is
+  fn implied_flags(&self) -> &'static [DietaryFlag] {
+    use DietaryFlag::*;
+    match self {
+      Dairy | Egg | Fish | Shellfish | Meat => &[AnimalProduct],
+      _ => &[],
+    }
+  }
+}
+
+impl fmt::Display for DietaryFlag {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    use DietaryFlag::*;
+    let name = match self {
+      Gluten => "Gluten",
+      Dairy => "Dairy",
+      Egg => "Egg",
+      Nuts => "Nuts",
+      Peanuts => "Peanuts",
+      Soy => "Soy",
+      Fish => "Fish",
+      Shellfish => "Shellfish",
+      Meat => "Meat",
+      AnimalProduct => "Animal Product",
+    };
+    write!(f, "{}", name)
+  }
+}
+
+impl Diet {
+  const ALL: [Diet; 6] = [
+    Diet::Vegan,
+    Diet::Vegetarian,
+    Diet::Pescatarian,
+    Diet::GlutenFree,
+    Diet::DairyFree,
+    Diet::NutFree,
+  ];
+
+  /// Recipes can't contain any of these and still fit the diet
+  fn excluded_flags(&self) -> &'static [DietaryFlag] {
+    use DietaryFlag::*;
+    match self {
+      Diet::Vegan => &[AnimalProduct],
+      Diet::Vegetarian => &[Meat, Fish, Shellfish],
+      Diet::Pescatarian => &[Meat],
+      Diet::GlutenFree => &[Gluten],
+      Diet::DairyFree => &[Dairy],
+      Diet::NutFree => &[Nuts, Peanuts],
+    }
+  }
+
+  /// Every recipe that fits this diet also fits these diets
+  fn implied_diets(&self) -> &'static [Diet] {
+    match self {
+      Diet::Vegan => &[Diet::Vegetarian, Diet::Pescatarian, Diet::DairyFree],
+      Diet::Vegetarian => &[Diet::Pescatarian],
+      _ => &[],
+    }
+  }
+}
+
+impl fmt::Display for Diet {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let name = match self {
+      Diet::Vegan => "Vegan",
+      Diet::Vegetarian => "Vegetarian",
+      Diet::Pescatarian => "Pescatarian",
+      Diet::GlutenFree => "Gluten-Free",
+      Diet::DairyFree => "Dairy-Free",
+      Diet::NutFree => "Nut-Free",
+    };
+    write!(f, "{}", name)
+  }
+}
+
+impl DietaryInfo {
+  pub fn add_ingredient(&mut self, ingredient: &Ingredient, flags: &[DietaryFlag]) {
+    if !ingredient.flags_reviewed {
+      self.unreviewed_ingredients.push(ingredient.name.clone());
+    }
+    for flag in flags {
+      self.contains.insert(*flag);
+      self.contains.extend(flag.implied_flags());
+    }
+  }
+
+  /// Only true if every ingredient has been reviewed
+  pub fn is_free_of(&self, flag: DietaryFlag) -> bool {
+    self.unreviewed_ingredients.is_empty() && !self.contains.contains(&flag)
+  }
+
+  /// True if an ingredient is known to go against the diet, unlike `fits_diet` which also needs everything reviewed
+  pub fn breaks_diet(&self, diet: Diet) -> bool {
+    diet.excluded_flags().iter().any(|flag| self.contains.contains(flag))
+  }
+
+  pub fn fits_diet(&self, diet: Diet) -> bool {
+    diet.excluded_flags().iter().all(|flag| self.is_free_of(*flag))
+  }
+
+  /// Diets the recipe fits, leaving out any that are implied by another (vegan is also vegetarian)
+  pub fn diets(&self) -> Vec<Diet> {
+    let all_diets: Vec<_> = Diet::ALL.into_iter().filter(|diet| self.fits_diet(*diet)).collect();
+    all_diets
+      .iter()
+      .filter(|diet| !all_diets.iter().any(|other| other.implied_diets().contains(diet)))
+      .copied()
+      .collect()
+  }
+
+  /// Line of badges like "`Vegetarian` `Nut-Free` **Contains:** Dairy, Egg"
+  pub fn badges_markdown(&self) -> String {
+    let diets = self.diets().into_iter().map(|diet| format!("`{}`", diet));
+    let contains = (!self.contains.is_empty()).then(|| format!("**Contains:** {}", self.contains.iter().join(", ")));
+    diets.chain(contains).join(" ")
+  }
+}
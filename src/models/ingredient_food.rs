@@ -0,0 +1,37 @@
+use diesel::prelude::*;
+
+use crate::models::{Food, Ingredient};
+use crate::quantity::Conversions;
+use crate::schema::ingredient_foods;
+
+/// Links an ingredient to its entry in the nutrient database
+#[derive(Debug, Clone, Queryable, Insertable, Identifiable, AsChangeset, Associations)]
+#[diesel(primary_key(ingredient_id))]
+#[diesel(treat_none_as_null = true)]
+#[diesel(belongs_to(Ingredient))]
+#[diesel(belongs_to(Food))]
+pub struct IngredientFood {
+  pub ingredient_id: i32,
+  pub food_id: i32,
+  pub grams_per_ml: Option<f64>,
+  pub grams_per_each: Option<f64>,
+}
+
+impl IngredientFood {
+  model_base!();
+  belongs_to!(Ingredient);
+  belongs_to!(Food);
+
+  /// Conversions set on the link take priority over the ones from the food
+  pub fn conversions(&self, food: &Food, conn: &mut SqliteConnection) -> QueryResult<Conversions> {
+    Ok(Conversions {
+      grams_per_ml: self.grams_per_ml.or(food.grams_per_ml),
+      grams_per_each: self.grams_per_each.or(food.grams_per_each),
+      grams_per_portion: food
+        .get_food_portions(conn)?
+        .into_iter()
+        .filter_map(|portion| Some((portion.get_unit()?, portion.grams)))
+        .collect(),
+    })
+  }
+}
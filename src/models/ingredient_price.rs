@@ -0,0 +1,88 @@
+use diesel::prelude::*;
+
+use super::IngredientGap;
+use crate::models::Ingredient;
+use crate::quantity::{Conversions, Quantity};
+use crate::schema::ingredient_prices;
+
+#[derive(Debug, Clone, Queryable, Insertable, Identifiable, Associations)]
+#[diesel(treat_none_as_null = true)]
+#[diesel(belongs_to(Ingredient))]
+pub struct IngredientPrice {
+  pub id: i32,
+  pub ingredient_id: i32,
+  pub price_cents: i32,
+  pub package_quantity: String,
+  pub store: Option<String>,
+  pub recorded_on: String,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = ingredient_prices)]
+pub struct IngredientPriceConstructor<'s> {
+  pub ingredient_id: i32,
+  pub price_cents: i32,
+  pub package_quantity: &'s str,
+  pub store: Option<&'s str>,
+  pub recorded_on: Option<&'s str>, // Defaults to today
+}
+
+/// Estimated cost of a recipe, along with any ingredients that couldn't be priced
+#[derive(Debug, Clone, Default)]
+pub struct CostEstimate {
+  pub lines: Vec<CostLine>,
+  pub gaps: Vec<IngredientGap>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CostLine {
+  pub ingredient: String,
+  pub quantity: String,
+  pub price: IngredientPrice,
+  pub cost_cents: f64,
+}
+
+impl IngredientPrice {
+  model_base!(no update, order by ingredient_prices::recorded_on.desc());
+  belongs_to!(Ingredient);
+
+  /// Most recent price for each ingredient wins
+  pub fn find_latest(ingredient_id: i32, conn: &mut SqliteConnection) -> QueryResult<Option<Self>> {
+    ingredient_prices::table
+      .filter(ingredient_prices::ingredient_id.eq(ingredient_id))
+      .order_by((ingredient_prices::recorded_on.desc(), ingredient_prices::id.desc()))
+      .first(conn)
+      .optional()
+  }
+
+  /// Price of part of the package, converting between weight, volume, and count if needed
+  ///
+  /// Returns None if the package size can't be parsed or the units can't be converted
+  pub fn cost_of(&self, quantity: &Quantity, conversions: &Conversions) -> Option<f64> {
+    let package = Quantity::parse(&self.package_quantity)?;
+    let ratio = quantity.measure().ratio_to(package.measure(), conversions)?;
+    Some(ratio * self.price_cents as f64)
+  }
+}
+
+impl IngredientPriceConstructor<'_> {
+  model_creates!(IngredientPrice);
+}
+
+impl CostEstimate {
+  pub fn add_gap(&mut self, ingredient: &str, reason: &str) {
+    self.gaps.push(IngredientGap {
+      ingredient: ingredient.into(),
+      reason: reason.into(),
+    });
+  }
+
+  pub fn total_cents(&self) -> f64 {
+    self.lines.iter().map(|line| line.cost_cents).sum()
+  }
+}
+
+/// Format an amount of money like "$12.34"
+pub fn format_cents(cents: f64) -> String {
+  format!("${:.2}", cents / 100.0)
+}
@@ -0,0 +1,152 @@
+use diesel::prelude::*;
+use itertools::Itertools;
+
+use crate::models::{Diet, DietaryInfo, Ingredient, SubstitutionComponent};
+use crate::quantity::Quantity;
+use crate::schema::{ingredient_substitutions, ingredients, substitution_components};
+
+#[derive(Debug, Clone, Queryable, Insertable, Identifiable, Associations)]
+#[diesel(treat_none_as_null = true)]
+#[diesel(belongs_to(Ingredient))]
+pub struct IngredientSubstitution {
+  pub id: i32,
+  pub ingredient_id: i32,
+  pub quantity: String,
+  pub notes_markdown: String,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = ingredient_substitutions)]
+pub struct IngredientSubstitutionConstructor<'s> {
+  pub ingredient_id: i32,
+  pub quantity: &'s str,
+  pub notes_markdown: &'s str,
+}
+
+/// Substitutions chosen for every ingredient of a recipe that doesn't fit a diet
+#[derive(Debug, Clone, Default)]
+pub struct SubstitutionPlan {
+  pub applied: Vec<AppliedSubstitution>,
+  pub missing: Vec<String>, // Ingredients without a substitution that fits
+}
+
+/// Substitution scaled to the amount used in a recipe
+#[derive(Debug, Clone)]
+pub struct AppliedSubstitution {
+  pub ingredient_id: i32,
+  pub original: String,
+  pub replacements: Vec<(Ingredient, String)>,
+  pub rule: String,
+  pub notes_markdown: String,
+}
+
+impl IngredientSubstitution {
+  model_base!(no update, order by ingredient_substitutions::id.asc());
+  belongs_to!(Ingredient);
+
+  pub fn get_components_with_ingredients(
+    &self,
+    conn: &mut SqliteConnection,
+  ) -> QueryResult<Vec<(SubstitutionComponent, Ingredient)>> {
+    substitution_components::table
+      .inner_join(ingredients::table)
+      .filter(substitution_components::substitution_id.eq(self.id))
+      .order_by(substitution_components::display_order.asc())
+      .get_results(conn)
+  }
+
+  /// Replace the components, keeping their order
+  pub fn set_components(&self, components: &[(i32, String)], conn: &mut SqliteConnection) -> QueryResult<()> {
+    conn.transaction(|conn| {
+      diesel::delete(substitution_components::table.filter(substitution_components::substitution_id.eq(self.id)))
+        .execute(conn)?;
+      SubstitutionComponent::insert_list(
+        &components
+          .iter()
+          .enumerate()
+          .map(|(i, (ingredient_id, quantity))| SubstitutionComponent {
+            substitution_id: self.id,
+            ingredient_id: *ingredient_id,
+            display_order: i as i32,
+            quantity: quantity.clone(),
+          })
+          .collect(),
+        conn,
+      )?;
+      Ok(())
+    })
+  }
+
+  /// Only true if every component has been reviewed and fits the diet
+  pub fn fits_diet(&self, diet: Diet, conn: &mut SqliteConnection) -> QueryResult<bool> {
+    let mut info = DietaryInfo::default();
+    for (_, ingredient) in self.get_components_with_ingredients(conn)? {
+      let flags = ingredient.get_dietary_flags(conn)?;
+      info.add_ingredient(&ingredient, &flags);
+    }
+    Ok(info.fits_diet(diet))
+  }
+
+  /// Readable form like "1 cup Buttermilk = 1 cup Milk + 1 Tbsp Lemon Juice"
+  pub fn describe(&self, conn: &mut SqliteConnection) -> QueryResult<String> {
+    let ingredient = self.get_ingredient(conn)?;
+    let components = self
+      .get_components_with_ingredients(conn)?
+      .into_iter()
+      .map(|(component, ingredient)| format_amount(&component.quantity, &ingredient.name))
+      .join(" + ");
+    Ok(format!(
+      "{} = {}",
+      format_amount(&self.quantity, &ingredient.name),
+      components
+    ))
+  }
+
+  /// Scale the components to replace the quantity used in a recipe
+  ///
+  /// Components are left unscaled if the quantities can't be compared.
+  pub fn apply(
+    &self,
+    ingredient: &Ingredient,
+    quantity: &str,
+    conn: &mut SqliteConnection,
+  ) -> QueryResult<AppliedSubstitution> {
+    let conversions = ingredient.get_conversions(conn)?;
+    let ratio = Quantity::parse(quantity)
+      .zip(Quantity::parse(&self.quantity))
+      .and_then(|(used, replaced)| used.measure().ratio_to(replaced.measure(), &conversions));
+
+    let replacements = self
+      .get_components_with_ingredients(conn)?
+      .into_iter()
+      .map(|(component, ingredient)| {
+        let quantity = match (ratio, Quantity::parse(&component.quantity)) {
+          (Some(ratio), Some(component_quantity)) => component_quantity.scaled(ratio).to_string(),
+          _ => component.quantity,
+        };
+        (ingredient, quantity)
+      })
+      .collect();
+
+    Ok(AppliedSubstitution {
+      ingredient_id: ingredient.id,
+      original: format_amount(quantity, &ingredient.name),
+      replacements,
+      rule: self.describe(conn)?,
+      notes_markdown: self.notes_markdown.clone(),
+    })
+  }
+}
+
+impl IngredientSubstitutionConstructor<'_> {
+  model_creates!(IngredientSubstitution);
+}
+
+fn format_amount(quantity: &str, name: &str) -> String {
+  let quantity = quantity.trim();
+  if quantity.is_empty() {
+    name.to_string()
+  } else {
+    format!("{} {}", quantity, name)
+  }
+}
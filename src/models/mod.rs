@@ -2,22 +2,56 @@
 mod associations;
 #[macro_use]
 mod creates;
+mod food;
+mod food_portion;
 mod ingredient;
+mod ingredient_alias;
+mod ingredient_category;
+mod ingredient_flag;
+mod ingredient_food;
+mod ingredient_price;
+mod ingredient_substitution;
 mod many_many_constructor;
 mod recipe;
+mod recipe_draft;
 mod recipe_ingredient;
+mod recipe_relation;
+mod recipe_revision;
+mod recipe_revision_ingredient;
+mod recipe_revision_tag;
+mod recipe_step;
+mod recipe_step_ingredient;
 mod recipe_tag;
+mod recipe_template;
+mod recipe_template_tag;
+mod substitution_component;
 mod tag;
 
+pub use food::*;
+pub use food_portion::*;
 pub use ingredient::*;
+pub use ingredient_alias::*;
+pub use ingredient_category::*;
+pub use ingredient_flag::*;
+pub use ingredient_food::*;
+pub use ingredient_price::*;
+pub use ingredient_substitution::*;
 pub use many_many_constructor::*;
 pub use recipe::*;
+pub use recipe_draft::*;
 pub use recipe_ingredient::*;
+pub use recipe_relation::*;
+pub use recipe_revision::*;
+pub use recipe_revision_ingredient::*;
+pub use recipe_revision_tag::*;
+pub use recipe_step::*;
+pub use recipe_step_ingredient::*;
 pub use recipe_tag::*;
+pub use recipe_template::*;
+pub use recipe_template_tag::*;
+pub use substitution_component::*;
 pub use tag::*;
 
 use diesel::prelude::*;
-use diesel::sql_types::Text;
 
 sql_function!(fn last_insert_rowid() -> Integer);
-sql_function!(fn lower(input: Text) -> Text);
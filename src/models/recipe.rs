@@ -1,10 +1,18 @@
 use convert_case::{Case, Casing};
 use diesel::prelude::*;
+use diesel::sql_types::{Nullable, Text};
 use gtmpl_derive::Gtmpl;
 use itertools::Itertools;
 
-use crate::models::{Ingredient, RecipeIngredient};
-use crate::schema::recipes;
+use crate::links::{find_links, LinkIndex, LinkStyle, RecipeLink};
+use crate::models::{
+  CostEstimate, CostLine, Diet, DietaryInfo, Ingredient, IngredientFood, IngredientPrice, NutritionReport,
+  RecipeIngredient, RecipeRelation, RecipeStep, RecipeStepConstructor, RelationKind, SubstitutionPlan,
+};
+use crate::name_match::normalize;
+use crate::quantity::{Measure, Quantity};
+use crate::schema::{recipe_steps, recipes};
+use crate::steps::parse_steps;
 
 #[derive(Debug, Clone, Queryable, Insertable, Identifiable, AsChangeset, Gtmpl)]
 #[diesel(treat_none_as_null = true)]
@@ -13,6 +21,7 @@ pub struct Recipe {
   pub name: String,
   pub instructions_markdown: String,
   pub notes_markdown: String,
+  pub deleted_at: Option<String>,
 }
 
 #[derive(Debug, Clone, Insertable)]
@@ -24,7 +33,7 @@ pub struct RecipeConstructor<'s> {
 }
 
 impl Recipe {
-  model_base!(order by recipes::name.asc());
+  model_base!(order by recipes::name.asc(), where recipes::deleted_at.is_null());
 
   has_many!(RecipeIngredient);
   has_many!(Ingredient through RecipeIngredient, order by recipe_ingredients::display_order.asc());
@@ -32,6 +41,53 @@ impl Recipe {
   has_many!(RecipeTag);
   has_many!(Tag through RecipeTag, order by tags::name.asc());
 
+  has_many!(RecipeRevision, order by recipe_revisions::revision.asc());
+
+  has_many!(RecipeStep, order by recipe_steps::step_number.asc());
+
+  /// Get all recipes that are in the trash, most recently deleted first
+  pub fn all_deleted(conn: &mut SqliteConnection) -> QueryResult<Vec<Self>> {
+    use crate::schema::recipes::dsl::{deleted_at, recipes};
+
+    recipes
+      .filter(deleted_at.is_not_null())
+      .order_by(deleted_at.desc())
+      .get_results(conn)
+  }
+
+  /// Permanently delete every recipe in the trash
+  pub fn empty_trash(conn: &mut SqliteConnection) -> QueryResult<usize> {
+    use crate::schema::recipes::dsl::{deleted_at, recipes};
+
+    diesel::delete(recipes.filter(deleted_at.is_not_null())).execute(conn)
+  }
+
+  pub fn is_deleted(&self) -> bool {
+    self.deleted_at.is_some()
+  }
+
+  /// Move the recipe into the trash
+  pub fn trash(&self, conn: &mut SqliteConnection) -> QueryResult<Self> {
+    use crate::schema::recipes::dsl::{deleted_at, recipes};
+
+    diesel::update(recipes.find(self.id))
+      .set(deleted_at.eq(diesel::dsl::sql::<Nullable<Text>>("CURRENT_TIMESTAMP")))
+      .execute(conn)?;
+
+    self.reload_new(conn)
+  }
+
+  /// Take the recipe back out of the trash
+  pub fn restore(&self, conn: &mut SqliteConnection) -> QueryResult<Self> {
+    use crate::schema::recipes::dsl::{deleted_at, recipes};
+
+    diesel::update(recipes.find(self.id))
+      .set(deleted_at.eq(None::<String>))
+      .execute(conn)?;
+
+    self.reload_new(conn)
+  }
+
   pub fn get_ingredients_with_metadata(
     &self,
     conn: &mut SqliteConnection,
@@ -46,27 +102,471 @@ impl Recipe {
       .get_results::<(Ingredient, RecipeIngredient)>(conn)
   }
 
-  pub fn markdown_string(&self, conn: &mut SqliteConnection) -> QueryResult<String> {
-    let ingredients_str = self
-      .get_ingredients_with_metadata(conn)?
+  /// Roll up the allergens and diets of every ingredient
+  pub fn get_dietary_info(&self, conn: &mut SqliteConnection) -> QueryResult<DietaryInfo> {
+    let mut info = DietaryInfo::default();
+    for ingredient in self.get_ingredients(conn)? {
+      let flags = ingredient.get_dietary_flags(conn)?;
+      info.add_ingredient(&ingredient, &flags);
+    }
+    Ok(info)
+  }
+
+  /// Add up the nutrients of every ingredient that is linked to a food and has a usable quantity
+  pub fn get_nutrition(&self, conn: &mut SqliteConnection) -> QueryResult<NutritionReport> {
+    let mut report = NutritionReport::default();
+    for (ingredient, recipe_ingredient) in self.get_ingredients_with_metadata(conn)? {
+      let Some(link) = IngredientFood::find_optional(&ingredient.id, conn)? else {
+        report.add_gap(&ingredient.name, "not linked to a food");
+        continue;
+      };
+      let Some(quantity) = Quantity::parse(&recipe_ingredient.quantity) else {
+        report.add_gap(&ingredient.name, "quantity not understood");
+        continue;
+      };
+
+      let food = link.get_food(conn)?;
+      let measure = quantity.measure();
+      match (measure.to_grams(&link.conversions(&food, conn)?), measure) {
+        (Some(grams), _) => report.total = report.total + food.facts_for_grams(grams),
+        (None, Measure::Portion(unit, _)) => {
+          report.add_gap(&ingredient.name, &format!("no weight known for one {}", unit))
+        },
+        (None, _) => report.add_gap(&ingredient.name, "no weight known for this unit"),
+      }
+    }
+    Ok(report)
+  }
+
+  /// Price every ingredient using its most recent price
+  pub fn get_cost_estimate(&self, conn: &mut SqliteConnection) -> QueryResult<CostEstimate> {
+    let mut estimate = CostEstimate::default();
+    for (ingredient, recipe_ingredient) in self.get_ingredients_with_metadata(conn)? {
+      let Some(price) = IngredientPrice::find_latest(ingredient.id, conn)? else {
+        estimate.add_gap(&ingredient.name, "no price recorded");
+        continue;
+      };
+      let Some(quantity) = Quantity::parse(&recipe_ingredient.quantity) else {
+        estimate.add_gap(&ingredient.name, "quantity not understood");
+        continue;
+      };
+
+      match price.cost_of(&quantity, &ingredient.get_conversions(conn)?) {
+        Some(cost_cents) => estimate.lines.push(CostLine {
+          ingredient: ingredient.name,
+          quantity: recipe_ingredient.quantity,
+          price,
+          cost_cents,
+        }),
+        None => estimate.add_gap(&ingredient.name, "can't convert to the package size"),
+      }
+    }
+    Ok(estimate)
+  }
+
+  /// Copy the recipe with its ingredients and tags, and record the copy as a variation of this recipe
+  pub fn create_variant(&self, name: &str, conn: &mut SqliteConnection) -> QueryResult<Recipe> {
+    conn.transaction(|conn| {
+      let variant = RecipeConstructor {
+        name,
+        instructions_markdown: &self.instructions_markdown,
+        notes_markdown: &self.notes_markdown,
+      }
+      .insert_recipe(conn)?;
+
+      RecipeIngredient::insert_list(
+        &self
+          .get_recipe_ingredients(conn)?
+          .into_iter()
+          .map(|ri| RecipeIngredient {
+            recipe_id: variant.id,
+            ..ri
+          })
+          .collect(),
+        conn,
+      )?;
+      variant.set_tags_ids(self.get_tags(conn)?.iter().map(|t| &t.id), conn)?;
+
+      RecipeRelation {
+        recipe_id: variant.id,
+        related_recipe_id: self.id,
+        kind: RelationKind::VariationOf.id(),
+      }
+      .insert(conn)?;
+
+      variant.update_steps(conn)?;
+      Ok(variant)
+    })
+  }
+
+  /// How the ingredients differ from a recipe this is a variation of, like "Removed Butter"
+  pub fn get_ingredient_changes(&self, parent: &Recipe, conn: &mut SqliteConnection) -> QueryResult<Vec<String>> {
+    let ingredients = self.get_ingredients_with_metadata(conn)?;
+    let parent_ingredients = parent.get_ingredients_with_metadata(conn)?;
+
+    let mut changes = Vec::new();
+    for (parent_ingredient, parent_ri) in parent_ingredients.iter() {
+      match ingredients.iter().find(|(i, _)| i.id == parent_ingredient.id) {
+        None => changes.push(format!("Removed {}", parent_ingredient.name)),
+        Some((_, ri)) if ri.quantity.trim() != parent_ri.quantity.trim() => changes.push(format!(
+          "Changed {} from {} to {}",
+          parent_ingredient.name,
+          parent_ri.quantity.trim(),
+          ri.quantity.trim()
+        )),
+        Some(_) => {},
+      }
+    }
+    for (ingredient, ri) in ingredients.iter() {
+      if !parent_ingredients.iter().any(|(i, _)| i.id == ingredient.id) {
+        changes.push(format!("Added {} {}", ri.quantity.trim(), ingredient.name).replace("  ", " "));
+      }
+    }
+    Ok(changes)
+  }
+
+  /// Re-read the structured steps from the instructions
+  ///
+  /// Recipes without a numbered list end up with no steps.
+  pub fn update_steps(&self, conn: &mut SqliteConnection) -> QueryResult<Vec<RecipeStep>> {
+    conn.transaction(|conn| {
+      diesel::delete(recipe_steps::table.filter(recipe_steps::recipe_id.eq(self.id))).execute(conn)?;
+      let Some(parsed_steps) = parse_steps(&self.instructions_markdown) else {
+        return Ok(Vec::new());
+      };
+
+      // Steps often leave out part of the name, like "flour" for "all-purpose flour",
+      //   so the last word also counts as long as no other ingredient ends with it
+      let ingredients = self.get_ingredients(conn)?;
+      let mut names: Vec<(i32, Vec<String>)> = Vec::new();
+      for ingredient in ingredients.iter() {
+        names.push((ingredient.id, normalize(&ingredient.name)));
+        for alias in ingredient.get_ingredient_aliases(conn)? {
+          names.push((ingredient.id, normalize(&alias.name)));
+        }
+      }
+      let last_words = ingredients
+        .iter()
+        .filter_map(|ingredient| Some((ingredient.id, normalize(&ingredient.name).pop()?)))
+        .collect_vec();
+      for (id, word) in last_words.iter() {
+        if last_words.iter().filter(|(_, other)| other == word).count() == 1 {
+          names.push((*id, vec![word.clone()]));
+        }
+      }
+
+      let mut steps = Vec::new();
+      for (parsed_step, step_number) in parsed_steps.into_iter().zip(1..) {
+        let step = RecipeStepConstructor {
+          recipe_id: self.id,
+          step_number,
+          text_markdown: &parsed_step.text_markdown,
+          duration_seconds: parsed_step.duration_seconds,
+          temperature: parsed_step.temperature.as_deref(),
+        }
+        .insert_recipe_step(conn)?;
+
+        let words = normalize(&step.text_markdown);
+        let ingredient_ids = names
+          .iter()
+          .filter(|(_, name)| !name.is_empty() && words.windows(name.len()).any(|window| window == name.as_slice()))
+          .map(|(id, _)| *id)
+          .unique()
+          .collect_vec();
+        step.set_ingredients_ids(ingredient_ids.iter(), conn)?;
+
+        steps.push(step);
+      }
+      Ok(steps)
+    })
+  }
+
+  /// Stored steps, reading them from the instructions first if the recipe was saved before steps existed
+  pub fn get_or_update_steps(&self, conn: &mut SqliteConnection) -> QueryResult<Vec<RecipeStep>> {
+    let steps = self.get_recipe_steps_ordered(conn)?;
+    // Nothing to store without a numbered list, so don't write on every read
+    if steps.is_empty() && parse_steps(&self.instructions_markdown).is_some() {
+      self.update_steps(conn)
+    } else {
+      Ok(steps)
+    }
+  }
+
+  /// Pick a substitution for every ingredient that is known to go against the diet
+  pub fn get_substitutions(&self, diet: Diet, conn: &mut SqliteConnection) -> QueryResult<SubstitutionPlan> {
+    let mut plan = SubstitutionPlan::default();
+    for (ingredient, recipe_ingredient) in self.get_ingredients_with_metadata(conn)? {
+      let mut info = DietaryInfo::default();
+      info.add_ingredient(&ingredient, &ingredient.get_dietary_flags(conn)?);
+      if !info.breaks_diet(diet) {
+        continue;
+      }
+
+      let mut substitution = None;
+      for candidate in ingredient.get_ingredient_substitutions_ordered(conn)? {
+        if candidate.fits_diet(diet, conn)? {
+          substitution = Some(candidate);
+          break;
+        }
+      }
+
+      match substitution {
+        Some(substitution) => plan
+          .applied
+          .push(substitution.apply(&ingredient, &recipe_ingredient.quantity, conn)?),
+        None => plan.missing.push(ingredient.name),
+      }
+    }
+    Ok(plan)
+  }
+
+  pub fn markdown_string(&self, links: &LinkIndex, conn: &mut SqliteConnection) -> QueryResult<String> {
+    self.markdown_string_with_substitutions(&SubstitutionPlan::default(), links, conn)
+  }
+
+  /// Markdown with substituted ingredients swapped in and listed after the notes
+  pub fn markdown_string_with_substitutions(
+    &self,
+    plan: &SubstitutionPlan,
+    links: &LinkIndex,
+    conn: &mut SqliteConnection,
+  ) -> QueryResult<String> {
+    // Badges describe the recipe as it will actually be made
+    let mut dietary_info = DietaryInfo::default();
+    let mut ingredient_lines = Vec::new();
+    for (ingredient, recipe_ingredient) in self.get_ingredients_with_metadata(conn)? {
+      match plan.applied.iter().find(|s| s.ingredient_id == ingredient.id) {
+        None => {
+          dietary_info.add_ingredient(&ingredient, &ingredient.get_dietary_flags(conn)?);
+          ingredient_lines.push(ingredient.markdown_string(&recipe_ingredient));
+        },
+        Some(substitution) => {
+          for (replacement, quantity) in substitution.replacements.iter() {
+            dietary_info.add_ingredient(replacement, &replacement.get_dietary_flags(conn)?);
+            let instead_of = format!("*Instead of {}*", substitution.original);
+            let notes_markdown = match recipe_ingredient.notes_markdown.trim() {
+              "" => instead_of,
+              notes => format!("{}, {}", instead_of, notes),
+            };
+            let line = RecipeIngredient {
+              quantity: quantity.clone(),
+              notes_markdown,
+              ..recipe_ingredient.clone()
+            };
+            ingredient_lines.push(replacement.markdown_string(&line));
+          }
+        },
+      }
+    }
+    let ingredients_str = ingredient_lines.join("\n");
+
+    let badges = dietary_info.badges_markdown();
+    let badges = if !badges.is_empty() {
+      format!("{}\n\n", badges)
+    } else {
+      "".into()
+    };
+
+    let backlinks = links
+      .backlinks(self.id)
+      .into_iter()
+      .map(|other| format!("- {}", links.link_markdown(other)))
+      .collect_vec();
+    let used_in = if !backlinks.is_empty() {
+      format!("\n**Used in:**\n{}\n", backlinks.join("\n"))
+    } else {
+      "".into()
+    };
+
+    // Recipes this is a variant of get their own section with the ingredient changes
+    let (parents, related): (Vec<_>, Vec<_>) = self
+      .get_related_recipes(conn)?
       .into_iter()
-      .map(|(i, ri)| i.markdown_string(&ri))
-      .join("\n");
+      .partition(|(kind, reverse, _)| (*kind, *reverse) == (RelationKind::VariationOf, false));
 
-    let notes = if !self.notes_markdown.is_empty() {
-      format!("\n**Notes:**\n{}", self.notes_markdown)
+    let related = related
+      .into_iter()
+      .map(|(kind, reverse, recipes)| {
+        let recipe_links = recipes.iter().map(|r| links.link_markdown(r)).join(", ");
+        format!("- {}: {}", kind.label(reverse), recipe_links)
+      })
+      .collect_vec();
+    let related = if !related.is_empty() {
+      format!("\n**Related:**\n{}\n", related.join("\n"))
+    } else {
+      "".into()
+    };
+
+    let mut variant_changes = String::new();
+    for parent in parents.into_iter().flat_map(|(_, _, recipes)| recipes) {
+      let changes = match self.get_ingredient_changes(&parent, conn)? {
+        changes if changes.is_empty() => "- Same ingredients".to_string(),
+        changes => changes.iter().map(|change| format!("- {}", change)).join("\n"),
+      };
+      variant_changes.push_str(&format!(
+        "\n**Variant of {}:**\n{}\n",
+        links.link_markdown(&parent),
+        changes
+      ));
+    }
+
+    let substitutions = if !plan.applied.is_empty() || !plan.missing.is_empty() {
+      let applied = plan.applied.iter().map(|s| {
+        let notes = if !s.notes_markdown.is_empty() {
+          format!(" ({})", s.notes_markdown)
+        } else {
+          "".into()
+        };
+        format!("- {}{}", s.rule, notes)
+      });
+      let missing = (!plan.missing.is_empty()).then(|| format!("- No substitution for: {}", plan.missing.join(", ")));
+      format!("\n**Substitutions:**\n{}\n", applied.chain(missing).join("\n"))
     } else {
       "".into()
     };
 
+    // Sections after the notes start on a new line, so they aren't read as part of the notes
+    let later_sections = [used_in, related, variant_changes, substitutions].concat();
+    let notes = match (self.notes_markdown.is_empty(), later_sections.is_empty()) {
+      (true, _) => "".into(),
+      (false, true) => format!("\n**Notes:**\n{}", links.resolve_links(&self.notes_markdown)),
+      (false, false) => format!("\n**Notes:**\n{}\n", links.resolve_links(&self.notes_markdown)),
+    };
+
     Ok(format!(
-      "# {}\n---\n## Ingredients\n{}\n\n## Instructions\n{}\n{}",
-      self.name, ingredients_str, self.instructions_markdown, notes,
+      "# {}\n---\n{}## Ingredients\n{}\n\n## Instructions\n{}\n{}{}",
+      self.name,
+      badges,
+      ingredients_str,
+      links.resolve_links(&self.instructions_markdown),
+      notes,
+      later_sections,
     ))
   }
 
+  /// Links to other recipes in the instructions and notes
+  pub fn get_links(&self) -> Vec<RecipeLink<'_>> {
+    find_links(&self.instructions_markdown)
+      .into_iter()
+      .chain(find_links(&self.notes_markdown))
+      .collect()
+  }
+
+  /// Recipes this one links to or is related to, along with the recipes that link to it
+  pub fn get_connected_recipes(&self, links: &LinkIndex, conn: &mut SqliteConnection) -> QueryResult<Vec<Recipe>> {
+    let related = self
+      .get_related_recipes(conn)?
+      .into_iter()
+      .flat_map(|(_, _, recipes)| recipes);
+    let linked = links
+      .linked_recipes(self)
+      .into_iter()
+      .chain(links.backlinks(self.id))
+      .cloned();
+
+    Ok(
+      related
+        .chain(linked)
+        .filter(|other| other.id != self.id)
+        .unique_by(|other| other.id)
+        .collect(),
+    )
+  }
+
+  /// Related recipes grouped by kind and whether this recipe is on the receiving end, leaving out recipes in the trash
+  pub fn get_related_recipes(
+    &self,
+    conn: &mut SqliteConnection,
+  ) -> QueryResult<Vec<(RelationKind, bool, Vec<Recipe>)>> {
+    let relations = RecipeRelation::find_for_recipe(self.id, conn)?
+      .into_iter()
+      .filter_map(|relation| {
+        let kind = relation.get_kind()?;
+        let other_id = if relation.recipe_id == self.id {
+          relation.related_recipe_id
+        } else {
+          relation.recipe_id
+        };
+
+        // "Pairs with" reads the same both ways
+        let reverse = relation.recipe_id != self.id && kind != RelationKind::PairsWith;
+        Some((kind, reverse, other_id))
+      })
+      .sorted();
+
+    let mut groups: Vec<(RelationKind, bool, Vec<Recipe>)> = Vec::new();
+    for (kind, reverse, other_id) in relations {
+      let other = Self::find(&other_id, conn)?;
+      if other.is_deleted() {
+        continue;
+      }
+
+      match groups.last_mut() {
+        Some((last_kind, last_reverse, recipes)) if (*last_kind, *last_reverse) == (kind, reverse) => {
+          recipes.push(other)
+        },
+        _ => groups.push((kind, reverse, vec![other])),
+      }
+    }
+
+    for (_, _, recipes) in groups.iter_mut() {
+      recipes.sort_by(|a, b| a.name.cmp(&b.name));
+      recipes.dedup_by_key(|r| r.id);
+    }
+    Ok(groups)
+  }
+
+  pub fn link_markdown(&self, style: LinkStyle) -> String {
+    match style {
+      LinkStyle::Terminal => format!("{} (#{})", self.name, self.id),
+      LinkStyle::Markdown => format!("[{}]({})", self.name, self.get_filename()),
+      LinkStyle::Html => format!("[{}]({})", self.name, self.get_html_filename()),
+    }
+  }
+
+  /// Find all recipes matching an ID, name, slug, or unique prefix of the name or slug
+  ///
+  /// Exact matches take priority over prefix matches, so "pie" won't match "Pie Crust" if "Pie" exists.
+  /// An ID can match a recipe in the trash, but names and slugs only match recipes that aren't.
+  pub fn find_by_reference(reference: &str, conn: &mut SqliteConnection) -> QueryResult<Vec<Self>> {
+    let reference = reference.trim();
+    if let Ok(id) = reference.parse::<i32>() {
+      if let Some(recipe) = Self::find_optional(&id, conn)? {
+        return Ok(vec![recipe]);
+      }
+    }
+
+    let name = reference.to_lowercase();
+    let slug = reference.to_case(Case::Kebab);
+    let all_recipes = Self::all_ordered(conn)?;
+
+    let exact_matches: Vec<_> = all_recipes
+      .iter()
+      .filter(|r| r.name.to_lowercase() == name || r.get_slug() == slug)
+      .cloned()
+      .collect();
+    if !exact_matches.is_empty() {
+      return Ok(exact_matches);
+    }
+
+    Ok(
+      all_recipes
+        .into_iter()
+        .filter(|r| r.name.to_lowercase().starts_with(&name) || r.get_slug().starts_with(&slug))
+        .collect(),
+    )
+  }
+
+  pub fn get_slug(&self) -> String {
+    self.name.to_case(Case::Kebab)
+  }
+
   pub fn get_filename(&self) -> String {
-    format!("{}.md", self.name.to_case(Case::Kebab))
+    format!("{}.md", self.get_slug())
+  }
+
+  pub fn get_html_filename(&self) -> String {
+    format!("{}.html", self.get_slug())
   }
 }
 
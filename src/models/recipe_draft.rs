@@ -0,0 +1,56 @@
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::Text;
+
+use crate::schema::recipe_drafts;
+
+#[derive(Debug, Clone, Queryable, Insertable, Identifiable)]
+#[diesel(treat_none_as_null = true)]
+pub struct RecipeDraft {
+  pub id: i32,
+  pub recipe_id: Option<i32>,
+  pub updated_at: String,
+  pub name: String,
+  pub editor_state: String,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = recipe_drafts)]
+pub struct RecipeDraftConstructor<'s> {
+  pub recipe_id: Option<i32>,
+  pub name: &'s str,
+  pub editor_state: &'s str,
+}
+
+impl RecipeDraft {
+  model_base!(no update, order by recipe_drafts::updated_at.desc());
+
+  /// Drafts for an existing recipe, or drafts of new recipes if no ID is given
+  pub fn find_for_recipe(recipe_id: Option<i32>, conn: &mut SqliteConnection) -> QueryResult<Vec<Self>> {
+    let query = recipe_drafts::table
+      .order_by(recipe_drafts::updated_at.desc())
+      .into_boxed();
+    match recipe_id {
+      Some(id) => query.filter(recipe_drafts::recipe_id.eq(id)),
+      None => query.filter(recipe_drafts::recipe_id.is_null()),
+    }
+    .get_results(conn)
+  }
+
+  /// Overwrite the saved editor state
+  pub fn update_state(&self, name: &str, editor_state: &str, conn: &mut SqliteConnection) -> QueryResult<Self> {
+    diesel::update(recipe_drafts::table.find(self.id))
+      .set((
+        recipe_drafts::name.eq(name),
+        recipe_drafts::editor_state.eq(editor_state),
+        recipe_drafts::updated_at.eq(sql::<Text>("CURRENT_TIMESTAMP")),
+      ))
+      .execute(conn)?;
+
+    self.reload_new(conn)
+  }
+}
+
+impl RecipeDraftConstructor<'_> {
+  model_creates!(RecipeDraft);
+}
@@ -0,0 +1,67 @@
+use clap::ValueEnum;
+use diesel::prelude::*;
+
+use crate::models::Recipe;
+use crate::schema::recipe_relations;
+
+/// Reads as: recipe <kind> related recipe
+#[derive(Debug, Clone, Queryable, Insertable, Identifiable, Associations)]
+#[diesel(primary_key(recipe_id, related_recipe_id, kind))]
+#[diesel(treat_none_as_null = true)]
+#[diesel(belongs_to(Recipe))]
+pub struct RecipeRelation {
+  pub recipe_id: i32,
+  pub related_recipe_id: i32,
+  pub kind: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum RelationKind {
+  PairsWith,
+  SideFor,
+  VariationOf,
+  Replaces,
+}
+
+impl RecipeRelation {
+  model_base!(no update);
+  belongs_to!(Recipe);
+
+  /// Relations in either direction, ignoring kinds this version doesn't know about
+  pub fn find_for_recipe(recipe_id: i32, conn: &mut SqliteConnection) -> QueryResult<Vec<Self>> {
+    use crate::schema::recipe_relations::dsl;
+
+    dsl::recipe_relations
+      .filter(dsl::recipe_id.eq(recipe_id).or(dsl::related_recipe_id.eq(recipe_id)))
+      .get_results::<Self>(conn)
+      .map(|relations| relations.into_iter().filter(|r| r.get_kind().is_some()).collect())
+  }
+
+  pub fn get_kind(&self) -> Option<RelationKind> {
+    RelationKind::from_id(&self.kind)
+  }
+}
+
+impl RelationKind {
+  pub fn id(&self) -> String {
+    self.to_possible_value().unwrap().get_name().to_string() // Will not panic, no variants are skipped
+  }
+
+  pub fn from_id(id: &str) -> Option<Self> {
+    <Self as ValueEnum>::from_str(id, true).ok()
+  }
+
+  /// Heading for the related recipes, from the point of view of either side of the relation
+  pub fn label(&self, reverse: bool) -> &'static str {
+    use RelationKind::*;
+    match (self, reverse) {
+      (PairsWith, _) => "Pairs with",
+      (SideFor, false) => "Side for",
+      (SideFor, true) => "Sides",
+      (VariationOf, false) => "Variant of",
+      (VariationOf, true) => "Variants",
+      (Replaces, false) => "Replaces",
+      (Replaces, true) => "Replaced by",
+    }
+  }
+}
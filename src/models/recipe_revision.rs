@@ -0,0 +1,177 @@
+use diesel::prelude::*;
+use gtmpl_derive::Gtmpl;
+use itertools::Itertools;
+
+use crate::models::{Ingredient, Recipe, RecipeIngredient, RecipeRevisionIngredient, Tag};
+use crate::schema::recipe_revisions;
+
+#[derive(Debug, Clone, Queryable, Insertable, Identifiable, Associations, Gtmpl)]
+#[diesel(treat_none_as_null = true)]
+#[diesel(belongs_to(Recipe))]
+pub struct RecipeRevision {
+  pub id: i32,
+  pub recipe_id: i32,
+  pub revision: i32,
+  pub created_at: String,
+  pub name: String,
+  pub instructions_markdown: String,
+  pub notes_markdown: String,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = recipe_revisions)]
+pub struct RecipeRevisionConstructor<'s> {
+  pub recipe_id: i32,
+  pub revision: i32,
+  pub name: &'s str,
+  pub instructions_markdown: &'s str,
+  pub notes_markdown: &'s str,
+}
+
+impl RecipeRevision {
+  model_base!(no update, order by recipe_revisions::revision.asc());
+  belongs_to!(Recipe);
+
+  has_many!(RecipeRevisionIngredient);
+  has_many!(RecipeRevisionTag);
+  has_many!(Tag through RecipeRevisionTag, order by tags::name.asc());
+
+  /// Save the current state of the recipe as its next revision
+  pub fn create_from_recipe(recipe: &Recipe, conn: &mut SqliteConnection) -> QueryResult<Self> {
+    use crate::schema::recipe_revisions::dsl::{recipe_id, recipe_revisions, revision};
+
+    let last_revision: Option<i32> = recipe_revisions
+      .filter(recipe_id.eq(recipe.id))
+      .select(diesel::dsl::max(revision))
+      .get_result(conn)?;
+
+    let new_revision = RecipeRevisionConstructor {
+      recipe_id: recipe.id,
+      revision: last_revision.unwrap_or(0) + 1,
+      name: &recipe.name,
+      instructions_markdown: &recipe.instructions_markdown,
+      notes_markdown: &recipe.notes_markdown,
+    }
+    .insert_recipe_revision(conn)?;
+
+    RecipeRevisionIngredient::insert_list(
+      &recipe
+        .get_recipe_ingredients(conn)?
+        .into_iter()
+        .map(|ri| RecipeRevisionIngredient::from_recipe_ingredient(new_revision.id, ri))
+        .collect(),
+      conn,
+    )?;
+
+    new_revision.set_tags_ids(recipe.get_tags(conn)?.iter().map(|t| &t.id), conn)?;
+
+    Ok(new_revision)
+  }
+
+  pub fn find_by_revision(
+    recipe_id: i32,
+    revision: i32,
+    conn: &mut SqliteConnection,
+  ) -> QueryResult<Option<RecipeRevision>> {
+    recipe_revisions::table
+      .filter(recipe_revisions::recipe_id.eq(recipe_id))
+      .filter(recipe_revisions::revision.eq(revision))
+      .get_result(conn)
+      .optional()
+  }
+
+  pub fn get_ingredients_with_metadata(
+    &self,
+    conn: &mut SqliteConnection,
+  ) -> QueryResult<Vec<(Ingredient, RecipeIngredient)>> {
+    use crate::schema::ingredients::dsl::ingredients;
+    use crate::schema::recipe_revision_ingredients::dsl::{
+      display_order, recipe_revision_id, recipe_revision_ingredients,
+    };
+
+    let results = ingredients
+      .inner_join(recipe_revision_ingredients)
+      .filter(recipe_revision_id.eq(self.id))
+      .order_by(display_order.asc())
+      .get_results::<(Ingredient, RecipeRevisionIngredient)>(conn)?;
+
+    Ok(
+      results
+        .into_iter()
+        .map(|(i, rri)| (i, rri.into_recipe_ingredient(self.recipe_id)))
+        .collect(),
+    )
+  }
+
+  /// Overwrite the recipe with the contents of this revision
+  pub fn restore(&self, recipe: &Recipe, conn: &mut SqliteConnection) -> QueryResult<Recipe> {
+    let restored_recipe = Recipe {
+      name: self.name.clone(),
+      instructions_markdown: self.instructions_markdown.clone(),
+      notes_markdown: self.notes_markdown.clone(),
+      ..recipe.clone()
+    }
+    .update(conn)?;
+
+    restored_recipe.delete_all_recipe_ingredients(conn)?;
+    RecipeIngredient::insert_list(
+      &self
+        .get_recipe_revision_ingredients(conn)?
+        .into_iter()
+        .map(|rri| rri.into_recipe_ingredient(restored_recipe.id))
+        .collect(),
+      conn,
+    )?;
+
+    restored_recipe.set_tags_ids(self.get_tags(conn)?.iter().map(|t| &t.id), conn)?;
+    restored_recipe.update_steps(conn)?;
+
+    Ok(restored_recipe)
+  }
+
+  /// Plain-text representation of the revision, used for diffs
+  pub fn diff_string(&self, conn: &mut SqliteConnection) -> QueryResult<String> {
+    Ok(build_diff_string(
+      &self.name,
+      &self.get_ingredients_with_metadata(conn)?,
+      &self.instructions_markdown,
+      &self.notes_markdown,
+      &self.get_tags_ordered(conn)?,
+    ))
+  }
+
+  /// Plain-text representation of the current recipe, used for diffs
+  pub fn recipe_diff_string(recipe: &Recipe, conn: &mut SqliteConnection) -> QueryResult<String> {
+    Ok(build_diff_string(
+      &recipe.name,
+      &recipe.get_ingredients_with_metadata(conn)?,
+      &recipe.instructions_markdown,
+      &recipe.notes_markdown,
+      &recipe.get_tags_ordered(conn)?,
+    ))
+  }
+}
+
+impl RecipeRevisionConstructor<'_> {
+  model_creates!(RecipeRevision);
+}
+
+fn build_diff_string(
+  name: &str,
+  ingredients: &[(Ingredient, RecipeIngredient)],
+  instructions_markdown: &str,
+  notes_markdown: &str,
+  tags: &[Tag],
+) -> String {
+  let ingredients_str = ingredients.iter().map(|(i, ri)| i.markdown_string(ri)).join("\n");
+  let tags_str = tags.iter().map(|t| &t.name).join(", ");
+
+  format!(
+    "# {}\n\n## Ingredients\n{}\n\n## Instructions\n{}\n\n## Notes\n{}\n\n## Tags\n{}\n",
+    name,
+    ingredients_str,
+    instructions_markdown.trim_end(),
+    notes_markdown.trim_end(),
+    tags_str
+  )
+}
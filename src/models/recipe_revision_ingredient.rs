@@ -0,0 +1,44 @@
+use diesel::prelude::*;
+use gtmpl_derive::Gtmpl;
+
+use crate::models::{Ingredient, RecipeIngredient, RecipeRevision};
+use crate::schema::recipe_revision_ingredients;
+
+#[derive(Debug, Clone, Queryable, Insertable, Identifiable, Associations, Gtmpl)]
+#[diesel(primary_key(recipe_revision_id, ingredient_id))]
+#[diesel(treat_none_as_null = true)]
+#[diesel(belongs_to(RecipeRevision))]
+#[diesel(belongs_to(Ingredient))]
+pub struct RecipeRevisionIngredient {
+  pub recipe_revision_id: i32,
+  pub ingredient_id: i32,
+  pub display_order: i32,
+  pub quantity: String,
+  pub notes_markdown: String,
+}
+
+impl RecipeRevisionIngredient {
+  model_base!(no update);
+  belongs_to!(RecipeRevision);
+  belongs_to!(Ingredient);
+
+  pub fn from_recipe_ingredient(recipe_revision_id: i32, recipe_ingredient: RecipeIngredient) -> Self {
+    Self {
+      recipe_revision_id,
+      ingredient_id: recipe_ingredient.ingredient_id,
+      display_order: recipe_ingredient.display_order,
+      quantity: recipe_ingredient.quantity,
+      notes_markdown: recipe_ingredient.notes_markdown,
+    }
+  }
+
+  pub fn into_recipe_ingredient(self, recipe_id: i32) -> RecipeIngredient {
+    RecipeIngredient {
+      recipe_id,
+      ingredient_id: self.ingredient_id,
+      display_order: self.display_order,
+      quantity: self.quantity,
+      notes_markdown: self.notes_markdown,
+    }
+  }
+}
@@ -0,0 +1,39 @@
+use diesel::prelude::*;
+use gtmpl_derive::Gtmpl;
+
+use crate::models::{ManyToManyConstructor, RecipeRevision, Tag};
+use crate::schema::recipe_revision_tags;
+
+#[derive(Debug, Clone, Queryable, Insertable, Identifiable, Associations, Gtmpl)]
+#[diesel(primary_key(recipe_revision_id, tag_id))]
+#[diesel(treat_none_as_null = true)]
+#[diesel(belongs_to(RecipeRevision))]
+#[diesel(belongs_to(Tag))]
+pub struct RecipeRevisionTag {
+  pub recipe_revision_id: i32,
+  pub tag_id: String,
+}
+
+impl RecipeRevisionTag {
+  model_base!(no update);
+  belongs_to!(RecipeRevision);
+  belongs_to!(Tag);
+}
+
+impl ManyToManyConstructor<RecipeRevision, Tag> for RecipeRevisionTag {
+  fn new(recipe_revision_id: &i32, tag_id: &String) -> Self {
+    Self {
+      recipe_revision_id: *recipe_revision_id,
+      tag_id: tag_id.clone(),
+    }
+  }
+}
+
+impl ManyToManyConstructor<Tag, RecipeRevision> for RecipeRevisionTag {
+  fn new(tag_id: &String, recipe_revision_id: &i32) -> Self {
+    Self {
+      recipe_revision_id: *recipe_revision_id,
+      tag_id: tag_id.clone(),
+    }
+  }
+}
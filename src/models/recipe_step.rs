@@ -0,0 +1,36 @@
+use diesel::prelude::*;
+
+use crate::models::Recipe;
+use crate::schema::recipe_steps;
+
+#[derive(Debug, Clone, Queryable, Insertable, Identifiable, Associations)]
+#[diesel(treat_none_as_null = true)]
+#[diesel(belongs_to(Recipe))]
+pub struct RecipeStep {
+  pub id: i32,
+  pub recipe_id: i32,
+  pub step_number: i32,
+  pub text_markdown: String,
+  pub duration_seconds: Option<i32>,
+  pub temperature: Option<String>,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = recipe_steps)]
+pub struct RecipeStepConstructor<'s> {
+  pub recipe_id: i32,
+  pub step_number: i32,
+  pub text_markdown: &'s str,
+  pub duration_seconds: Option<i32>,
+  pub temperature: Option<&'s str>,
+}
+
+impl RecipeStep {
+  model_base!(no update);
+  belongs_to!(Recipe);
+  has_many!(Ingredient through RecipeStepIngredient, order by ingredients::name.asc());
+}
+
+impl RecipeStepConstructor<'_> {
+  model_creates!(RecipeStep);
+}
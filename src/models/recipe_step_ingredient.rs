@@ -0,0 +1,27 @@
+use diesel::prelude::*;
+
+use crate::models::{Ingredient, ManyToManyConstructor, RecipeStep};
+use crate::schema::recipe_step_ingredients;
+
+#[derive(Debug, Clone, Queryable, Insertable, Identifiable, Associations)]
+#[diesel(primary_key(step_id, ingredient_id))]
+#[diesel(treat_none_as_null = true)]
+#[diesel(belongs_to(RecipeStep, foreign_key = step_id))]
+#[diesel(belongs_to(Ingredient))]
+pub struct RecipeStepIngredient {
+  pub step_id: i32,
+  pub ingredient_id: i32,
+}
+
+impl RecipeStepIngredient {
+  model_base!(no update);
+}
+
+impl ManyToManyConstructor<RecipeStep, Ingredient> for RecipeStepIngredient {
+  fn new(step_id: &i32, ingredient_id: &i32) -> Self {
+    Self {
+      step_id: *step_id,
+      ingredient_id: *ingredient_id,
+    }
+  }
+}
@@ -0,0 +1,18 @@
+use diesel::prelude::*;
+
+use crate::schema::recipe_templates;
+
+#[derive(Debug, Clone, Queryable, Insertable, Identifiable, AsChangeset)]
+#[diesel(treat_none_as_null = true)]
+pub struct RecipeTemplate {
+  pub id: String,
+  pub name: String,
+  pub ingredients_text: String,
+  pub instructions_markdown: String,
+  pub notes_markdown: String,
+}
+
+impl RecipeTemplate {
+  model_base!(order by recipe_templates::name.asc());
+  has_many!(Tag through RecipeTemplateTag, order by tags::name.asc());
+}
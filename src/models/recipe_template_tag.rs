@@ -0,0 +1,27 @@
+use diesel::prelude::*;
+
+use crate::models::{ManyToManyConstructor, RecipeTemplate, Tag};
+use crate::schema::recipe_template_tags;
+
+#[derive(Debug, Clone, Queryable, Insertable, Identifiable, Associations)]
+#[diesel(primary_key(template_id, tag_id))]
+#[diesel(treat_none_as_null = true)]
+#[diesel(belongs_to(RecipeTemplate, foreign_key = template_id))]
+#[diesel(belongs_to(Tag))]
+pub struct RecipeTemplateTag {
+  pub template_id: String,
+  pub tag_id: String,
+}
+
+impl RecipeTemplateTag {
+  model_base!(no update);
+}
+
+impl ManyToManyConstructor<RecipeTemplate, Tag> for RecipeTemplateTag {
+  fn new(template_id: &String, tag_id: &String) -> Self {
+    Self {
+      template_id: template_id.clone(),
+      tag_id: tag_id.clone(),
+    }
+  }
+}
@@ -0,0 +1,21 @@
+use diesel::prelude::*;
+
+use crate::models::{Ingredient, IngredientSubstitution};
+use crate::schema::substitution_components;
+
+#[derive(Debug, Clone, Queryable, Insertable, Identifiable, Associations)]
+#[diesel(primary_key(substitution_id, ingredient_id))]
+#[diesel(treat_none_as_null = true)]
+#[diesel(belongs_to(IngredientSubstitution, foreign_key = substitution_id))]
+#[diesel(belongs_to(Ingredient))]
+pub struct SubstitutionComponent {
+  pub substitution_id: i32,
+  pub ingredient_id: i32,
+  pub display_order: i32,
+  pub quantity: String,
+}
+
+impl SubstitutionComponent {
+  model_base!(no update);
+  belongs_to!(Ingredient);
+}
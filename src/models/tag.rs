@@ -14,5 +14,5 @@ pub struct Tag {
 impl Tag {
   model_base!(order by tags::name.asc());
   has_many!(RecipeTag);
-  has_many!(Recipe through RecipeTag, order by recipes::name.asc());
+  has_many!(Recipe through RecipeTag, order by recipes::name.asc(), where recipes::deleted_at.is_null());
 }
@@ -0,0 +1,119 @@
+use itertools::Itertools;
+use std::collections::BTreeSet;
+
+/// Scores at or above this are treated as the same name, like "Egg" and "eggs"
+pub const AUTO_ACCEPT_SCORE: f64 = 0.95;
+
+/// Scores below this aren't worth suggesting
+pub const MIN_SUGGEST_SCORE: f64 = 0.35;
+
+/// How similar two names are, from 0.0 (nothing in common) to 1.0 (same name)
+///
+/// Case, punctuation, and plural endings are ignored. The score combines edit distance, to catch typos,
+///   with the number of shared words, to catch reordered or extra words like "flour" and "all-purpose flour".
+pub fn similarity(a: &str, b: &str) -> f64 {
+  let a_words = normalize(a);
+  let b_words = normalize(b);
+  if a_words.is_empty() || b_words.is_empty() {
+    return 0.0;
+  }
+  if a_words == b_words {
+    return 1.0;
+  }
+
+  let edit_score = strsim::normalized_levenshtein(&a_words.join(" "), &b_words.join(" "));
+
+  let a_set: BTreeSet<_> = a_words.iter().collect();
+  let b_set: BTreeSet<_> = b_words.iter().collect();
+  let shared = a_set.intersection(&b_set).count() as f64;
+  let overlap_score = shared / a_set.union(&b_set).count() as f64;
+
+  // Every word of the shorter name appears in the longer name
+  let containment_score = shared / a_set.len().min(b_set.len()) as f64;
+
+  // Never quite reaches an exact match, so only normalized names are auto-accepted
+  (0.5 * edit_score + 0.25 * overlap_score + 0.25 * containment_score).min(AUTO_ACCEPT_SCORE - 0.01)
+}
+
+/// Lowercase words with punctuation and plural endings removed
+pub fn normalize(input: &str) -> Vec<String> {
+  input
+    .split(|c: char| !c.is_alphanumeric())
+    .filter(|word| !word.is_empty())
+    .map(|word| stem(&word.to_lowercase()))
+    .collect_vec()
+}
+
+/// Turn plural words into singular words (roughly)
+fn stem(word: &str) -> String {
+  if word.len() <= 3 {
+    return word.into();
+  }
+
+  if let Some(base) = word.strip_suffix("ies") {
+    return format!("{}y", base);
+  }
+
+  for suffix in ["oes", "ches", "shes", "sses", "xes", "zes"] {
+    if word.ends_with(suffix) {
+      return word[..word.len() - 2].into();
+    }
+  }
+
+  match word.strip_suffix('s') {
+    Some(base) if !base.ends_with('s') && !base.ends_with('u') => base.into(),
+    _ => word.into(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn plural_stemming() {
+    for (plural, singular) in [
+      ("eggs", "egg"),
+      ("berries", "berry"),
+      ("tomatoes", "tomato"),
+      ("peaches", "peach"),
+      ("radishes", "radish"),
+      ("boxes", "box"),
+      ("glass", "glass"),
+      ("asparagus", "asparagus"),
+      ("peas", "pea"),
+      ("ros", "ros"),
+    ] {
+      assert_eq!(stem(plural), singular);
+    }
+  }
+
+  #[test]
+  fn normalized_names_are_the_same() {
+    assert_eq!(normalize("All-Purpose  Flour!"), vec!["all", "purpose", "flour"]);
+    assert_eq!(similarity("Egg", "eggs"), 1.0);
+    assert_eq!(similarity("Cherry Tomatoes", "cherry tomato"), 1.0);
+    assert_eq!(similarity("", "eggs"), 0.0);
+  }
+
+  #[test]
+  fn word_order() {
+    let score = similarity("Flour, all-purpose", "all-purpose flour");
+    assert!(score >= MIN_SUGGEST_SCORE, "{}", score);
+    assert!(score < AUTO_ACCEPT_SCORE, "{}", score);
+    assert!(similarity("flour", "all-purpose flour") > similarity("flour", "sugar"));
+  }
+
+  #[test]
+  fn near_misses_are_not_accepted() {
+    for (a, b) in [
+      ("Salt", "Salts and pepper"),
+      ("Butter", "Buttermilk"),
+      ("Brown sugar", "Brown sugars, packed"),
+    ] {
+      let score = similarity(a, b);
+      assert!(score < AUTO_ACCEPT_SCORE, "{} / {}: {}", a, b, score);
+    }
+    assert!(similarity("Flour", "Sugar") < MIN_SUGGEST_SCORE);
+  }
+}
@@ -0,0 +1,549 @@
+use std::collections::HashMap;
+use std::fmt;
+
+/// Amount and unit written at the start of an ingredient line, like "1 1/2 cups"
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quantity {
+  pub amount: f64,
+  pub max_amount: Option<f64>, // Set for ranges like "2-3"
+  pub unit: Option<Unit>,
+  pub package_size: Option<(f64, Unit)>, // Size of each can or package, like the "15 oz" in "1 (15 oz) can"
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Unit {
+  Teaspoon,
+  Tablespoon,
+  Cup,
+  FluidOunce,
+  Pint,
+  Quart,
+  Gallon,
+  Milliliter,
+  Liter,
+  Ounce,
+  Pound,
+  Gram,
+  Kilogram,
+  Pinch,
+  Dash,
+  Clove,
+  Can,
+  Package,
+  Stick,
+  Slice,
+  Bunch,
+}
+
+/// Quantity converted to a base unit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Measure {
+  Grams(f64),
+  Milliliters(f64),
+  Count(f64),         // Whole items, like "3 eggs"
+  Portion(Unit, f64), // Units that only have a weight for a given food, like "2 cans" or "3 cloves"
+}
+
+/// Ingredient-specific conversions between weight, volume, and count
+#[derive(Debug, Clone, Default)]
+pub struct Conversions {
+  pub grams_per_ml: Option<f64>,
+  pub grams_per_each: Option<f64>,
+  pub grams_per_portion: HashMap<Unit, f64>,
+}
+
+/// Word in the input along with its byte offsets
+#[derive(Debug, Clone, Copy)]
+struct Token<'a> {
+  text: &'a str,
+  end: usize,
+}
+
+impl Quantity {
+  /// Parse a string that only contains a quantity
+  pub fn parse(input: &str) -> Option<Self> {
+    match Self::split_prefix(input) {
+      Some((quantity, _, "")) => Some(quantity),
+      _ => None,
+    }
+  }
+
+  /// Split a quantity off the start of the input
+  ///
+  /// Returns the parsed quantity, the text of the quantity, and the rest of the input.
+  pub fn split_prefix(input: &str) -> Option<(Self, &str, &str)> {
+    let tokens = tokenize(input);
+    let mut index = 0;
+
+    let next_unit = |index: usize| -> Option<(Unit, usize)> {
+      // Two-word units like "fl oz" come first
+      if let (Some(first), Some(second)) = (tokens.get(index), tokens.get(index + 1)) {
+        if let Some(unit) = Unit::from_word(&format!("{} {}", first.text, second.text)) {
+          return Some((unit, index + 2));
+        }
+      }
+      let unit = Unit::from_word(tokens.get(index)?.text)?;
+      Some((unit, index + 1))
+    };
+
+    // "a pinch of salt", but not "a little salt"
+    let (mut amount, mut max_amount) = match tokens.first().map(|t| t.text.to_lowercase()) {
+      Some(word) if (word == "a" || word == "an") && next_unit(1).is_some() => {
+        index += 1;
+        (1.0, None)
+      },
+      _ => {
+        let (amount, max_amount) = parse_range(tokens.first()?.text)?;
+        index += 1;
+        (amount, max_amount)
+      },
+    };
+
+    // Mixed numbers like "1 1/2"
+    if max_amount.is_none() && amount.fract() == 0.0 {
+      if let Some(fraction) = tokens.get(index).and_then(|t| parse_fraction(t.text)) {
+        amount += fraction;
+        index += 1;
+      }
+    }
+
+    // Ranges with spaces like "2 - 3" or "2 to 3"
+    if max_amount.is_none() {
+      if let (Some(separator), Some(max)) = (tokens.get(index), tokens.get(index + 1)) {
+        if matches!(separator.text, "-" | "–" | "to" | "or") {
+          if let Some(max) = parse_number(max.text) {
+            max_amount = Some(max);
+            index += 2;
+          }
+        }
+      }
+    }
+
+    // Package sizes like "1 (15 oz) can"
+    let mut package_size = None;
+    if tokens.get(index).is_some_and(|t| t.text.starts_with('(')) {
+      let close = index + tokens[index..].iter().position(|t| t.text.ends_with(')'))?;
+      let start = tokens[index].end - tokens[index].text.len();
+      let size = input[start..tokens[close].end]
+        .trim_start_matches('(')
+        .trim_end_matches(')');
+      package_size = Self::parse(size).and_then(|size| Some((size.amount, size.unit?)));
+      index = close + 1;
+    }
+
+    let unit = match next_unit(index) {
+      Some((unit, next_index)) => {
+        index = next_index;
+        if tokens.get(index).is_some_and(|t| t.text.eq_ignore_ascii_case("of")) {
+          index += 1;
+        }
+        Some(unit)
+      },
+      None => None,
+    };
+
+    let end = tokens[index - 1].end;
+    let quantity = Self {
+      amount,
+      max_amount,
+      unit,
+      package_size,
+    };
+    Some((quantity, input[..end].trim(), input[end..].trim()))
+  }
+}
+
+impl Quantity {
+  /// Convert to grams, milliliters, or a count, using the middle of any range
+  pub fn measure(&self) -> Measure {
+    let amount = match self.max_amount {
+      Some(max_amount) => (self.amount + max_amount) / 2.0,
+      None => self.amount,
+    };
+
+    // "2 (15 oz) cans" is 30 oz, but "2 (15 oz) cups" is still 2 cups
+    let size = match (self.unit.map(|unit| unit.base_measure()), self.package_size) {
+      (Some(Measure::Portion(..)), Some((size, unit))) => Some(unit.base_measure().scaled(size)),
+      (measure, _) => measure,
+    };
+    size.map_or(Measure::Count(amount), |size| size.scaled(amount))
+  }
+
+  /// Same quantity multiplied by a factor, like doubling a recipe
+  pub fn scaled(&self, factor: f64) -> Self {
+    Self {
+      amount: self.amount * factor,
+      max_amount: self.max_amount.map(|max_amount| max_amount * factor),
+      unit: self.unit,
+      package_size: self.package_size,
+    }
+  }
+}
+
+impl Measure {
+  pub fn to_grams(self, conversions: &Conversions) -> Option<f64> {
+    match self {
+      Measure::Grams(grams) => Some(grams),
+      Measure::Milliliters(ml) => Some(ml * conversions.grams_per_ml?),
+      Measure::Count(count) => Some(count * conversions.grams_per_each?),
+      Measure::Portion(unit, count) => Some(count * conversions.grams_per_portion.get(&unit)?),
+    }
+  }
+
+  fn scaled(self, factor: f64) -> Self {
+    match self {
+      Measure::Grams(grams) => Measure::Grams(grams * factor),
+      Measure::Milliliters(ml) => Measure::Milliliters(ml * factor),
+      Measure::Count(count) => Measure::Count(count * factor),
+      Measure::Portion(unit, count) => Measure::Portion(unit, count * factor),
+    }
+  }
+
+  /// How many of the other measure fit in this one, converting through grams if the kinds differ
+  pub fn ratio_to(self, other: Measure, conversions: &Conversions) -> Option<f64> {
+    let ratio = match (self, other) {
+      (Measure::Grams(a), Measure::Grams(b))
+      | (Measure::Milliliters(a), Measure::Milliliters(b))
+      | (Measure::Count(a), Measure::Count(b)) => a / b,
+      (Measure::Portion(unit_a, a), Measure::Portion(unit_b, b)) if unit_a == unit_b => a / b,
+      (a, b) => a.to_grams(conversions)? / b.to_grams(conversions)?,
+    };
+    ratio.is_finite().then_some(ratio)
+  }
+}
+
+impl fmt::Display for Quantity {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", format_number(self.amount))?;
+    if let Some(max_amount) = self.max_amount {
+      write!(f, "-{}", format_number(max_amount))?;
+    }
+    if let Some((size, unit)) = self.package_size {
+      write!(f, " ({} {})", format_number(size), unit.name(size != 1.0))?;
+    }
+    if let Some(unit) = self.unit {
+      let plural = self.max_amount.is_some() || self.amount != 1.0;
+      write!(f, " {}", unit.name(plural))?;
+    }
+    Ok(())
+  }
+}
+
+impl Unit {
+  /// Recognize common spellings and abbreviations of the unit
+  pub fn from_word(word: &str) -> Option<Self> {
+    use Unit::*;
+
+    let word = word.trim_end_matches(['.', ',']);
+    let unit = match word {
+      // Abbreviations where case matters
+      "t" | "tsp" => Teaspoon,
+      "T" | "Tbsp" | "TBSP" | "Tbs" | "tbs" | "tbsp" => Tablespoon,
+
+      _ => match word.to_lowercase().replace('.', "").as_str() {
+        "teaspoon" | "teaspoons" | "tsps" => Teaspoon,
+        "tablespoon" | "tablespoons" | "tbsps" => Tablespoon,
+        "c" | "cup" | "cups" => Cup,
+        "fl oz" | "fluid ounce" | "fluid ounces" | "floz" => FluidOunce,
+        "pt" | "pint" | "pints" => Pint,
+        "qt" | "quart" | "quarts" => Quart,
+        "gal" | "gallon" | "gallons" => Gallon,
+        "ml" | "milliliter" | "milliliters" | "millilitre" | "millilitres" => Milliliter,
+        "l" | "liter" | "liters" | "litre" | "litres" => Liter,
+        "oz" | "ounce" | "ounces" => Ounce,
+        "lb" | "lbs" | "pound" | "pounds" => Pound,
+        "g" | "gram" | "grams" => Gram,
+        "kg" | "kilogram" | "kilograms" => Kilogram,
+        "pinch" | "pinches" => Pinch,
+        "dash" | "dashes" => Dash,
+        "clove" | "cloves" => Clove,
+        "can" | "cans" => Can,
+        "package" | "packages" | "pkg" => Package,
+        "stick" | "sticks" => Stick,
+        "slice" | "slices" => Slice,
+        "bunch" | "bunches" => Bunch,
+        _ => return None,
+      },
+    };
+
+    Some(unit)
+  }
+
+  /// Size of one of this unit
+  pub fn base_measure(&self) -> Measure {
+    use Unit::*;
+
+    match self {
+      Teaspoon => Measure::Milliliters(4.929),
+      Tablespoon => Measure::Milliliters(14.787),
+      Cup => Measure::Milliliters(236.588),
+      FluidOunce => Measure::Milliliters(29.574),
+      Pint => Measure::Milliliters(473.176),
+      Quart => Measure::Milliliters(946.353),
+      Gallon => Measure::Milliliters(3785.41),
+      Milliliter => Measure::Milliliters(1.0),
+      Liter => Measure::Milliliters(1000.0),
+      Pinch => Measure::Milliliters(0.308),
+      Dash => Measure::Milliliters(0.616),
+      Ounce => Measure::Grams(28.3495),
+      Pound => Measure::Grams(453.592),
+      Gram => Measure::Grams(1.0),
+      Kilogram => Measure::Grams(1000.0),
+      Clove | Can | Package | Stick | Slice | Bunch => Measure::Portion(*self, 1.0),
+    }
+  }
+
+  /// Short name of the unit, where abbreviations stay the same in the plural
+  pub fn name(&self, plural: bool) -> &'static str {
+    use Unit::*;
+
+    match (self, plural) {
+      (Teaspoon, _) => "tsp",
+      (Tablespoon, _) => "Tbsp",
+      (Cup, false) => "cup",
+      (Cup, true) => "cups",
+      (FluidOunce, _) => "fl oz",
+      (Pint, false) => "pint",
+      (Pint, true) => "pints",
+      (Quart, false) => "quart",
+      (Quart, true) => "quarts",
+      (Gallon, false) => "gallon",
+      (Gallon, true) => "gallons",
+      (Milliliter, _) => "ml",
+      (Liter, _) => "l",
+      (Ounce, _) => "oz",
+      (Pound, _) => "lb",
+      (Gram, _) => "g",
+      (Kilogram, _) => "kg",
+      (Pinch, false) => "pinch",
+      (Pinch, true) => "pinches",
+      (Dash, false) => "dash",
+      (Dash, true) => "dashes",
+      (Clove, false) => "clove",
+      (Clove, true) => "cloves",
+      (Can, false) => "can",
+      (Can, true) => "cans",
+      (Package, false) => "package",
+      (Package, true) => "packages",
+      (Stick, false) => "stick",
+      (Stick, true) => "sticks",
+      (Slice, false) => "slice",
+      (Slice, true) => "slices",
+      (Bunch, false) => "bunch",
+      (Bunch, true) => "bunches",
+    }
+  }
+}
+
+impl fmt::Display for Unit {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.name(false))
+  }
+}
+
+fn tokenize(input: &str) -> Vec<Token<'_>> {
+  let mut tokens = Vec::new();
+  let mut start = None;
+  for (index, c) in input.char_indices().chain(std::iter::once((input.len(), ' '))) {
+    match (c.is_whitespace(), start) {
+      (true, Some(token_start)) => {
+        tokens.push(Token {
+          text: &input[token_start..index],
+          end: index,
+        });
+        start = None;
+      },
+      (false, None) => start = Some(index),
+      _ => {},
+    }
+  }
+  tokens
+}
+
+/// Parse either a single number or a range like "2-3"
+fn parse_range(input: &str) -> Option<(f64, Option<f64>)> {
+  if let Some(number) = parse_number(input) {
+    return Some((number, None));
+  }
+
+  let (min, max) = input.split_once(['-', '–'])?;
+  Some((parse_number(min)?, Some(parse_number(max)?)))
+}
+
+/// Parse whole numbers, decimals, fractions, and number words
+fn parse_number(input: &str) -> Option<f64> {
+  let input = input.trim_end_matches(',');
+  if let Some(fraction) = parse_fraction(input) {
+    return Some(fraction);
+  }
+
+  // Whole number with a unicode fraction attached, like "1½"
+  if let Some(last) = input.chars().last() {
+    if let Some(fraction) = unicode_fraction(last) {
+      let whole = &input[..input.len() - last.len_utf8()];
+      if !whole.is_empty() && whole.chars().all(|c| c.is_ascii_digit()) {
+        return Some(whole.parse::<f64>().ok()? + fraction);
+      }
+    }
+  }
+
+  if input.chars().all(|c| c.is_ascii_digit() || c == '.') && input.chars().any(|c| c.is_ascii_digit()) {
+    return input.parse().ok();
+  }
+
+  let number = match input.to_lowercase().as_str() {
+    "one" => 1,
+    "two" => 2,
+    "three" => 3,
+    "four" => 4,
+    "five" => 5,
+    "six" => 6,
+    "seven" => 7,
+    "eight" => 8,
+    "nine" => 9,
+    "ten" => 10,
+    "eleven" => 11,
+    "twelve" | "dozen" => 12,
+    _ => return None,
+  };
+  Some(number as f64)
+}
+
+/// Parse "1/2" or "½"
+fn parse_fraction(input: &str) -> Option<f64> {
+  let mut chars = input.chars();
+  if let (Some(c), None) = (chars.next(), chars.next()) {
+    return unicode_fraction(c);
+  }
+
+  let (numerator, denominator) = input.split_once(['/', '⁄'])?;
+  let numerator: u32 = numerator.parse().ok()?;
+  let denominator: u32 = denominator.parse().ok()?;
+  (denominator != 0).then(|| numerator as f64 / denominator as f64)
+}
+
+fn unicode_fraction(c: char) -> Option<f64> {
+  let value = match c {
+    '½' => 1.0 / 2.0,
+    '⅓' => 1.0 / 3.0,
+    '⅔' => 2.0 / 3.0,
+    '¼' => 1.0 / 4.0,
+    '¾' => 3.0 / 4.0,
+    '⅕' => 1.0 / 5.0,
+    '⅖' => 2.0 / 5.0,
+    '⅗' => 3.0 / 5.0,
+    '⅘' => 4.0 / 5.0,
+    '⅙' => 1.0 / 6.0,
+    '⅚' => 5.0 / 6.0,
+    '⅛' => 1.0 / 8.0,
+    '⅜' => 3.0 / 8.0,
+    '⅝' => 5.0 / 8.0,
+    '⅞' => 7.0 / 8.0,
+    _ => return None,
+  };
+  Some(value)
+}
+
+/// Show at most two decimal places, without trailing zeros
+fn format_number(number: f64) -> String {
+  let formatted = format!("{:.2}", number);
+  formatted.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn split(input: &str) -> (Quantity, &str, &str) {
+    Quantity::split_prefix(input).unwrap()
+  }
+
+  #[test]
+  fn mixed_numbers() {
+    let (quantity, text, rest) = split("1 1/2 cups flour");
+    assert_eq!(quantity.amount, 1.5);
+    assert_eq!(quantity.unit, Some(Unit::Cup));
+    assert_eq!(text, "1 1/2 cups");
+    assert_eq!(rest, "flour");
+  }
+
+  #[test]
+  fn unicode_fractions() {
+    assert_eq!(split("½ tsp salt").0.amount, 0.5);
+    assert_eq!(split("1½ tsp salt").0.amount, 1.5);
+    assert_eq!(split("2 ¾ cups milk").0.amount, 2.75);
+  }
+
+  #[test]
+  fn ranges() {
+    for input in ["2-3 cloves garlic", "2 - 3 cloves garlic", "2 to 3 cloves garlic"] {
+      let (quantity, _, rest) = split(input);
+      assert_eq!((quantity.amount, quantity.max_amount), (2.0, Some(3.0)), "{}", input);
+      assert_eq!(quantity.unit, Some(Unit::Clove));
+      assert_eq!(rest, "garlic");
+    }
+  }
+
+  #[test]
+  fn a_pinch_of() {
+    let (quantity, text, rest) = split("a pinch of salt");
+    assert_eq!(quantity.amount, 1.0);
+    assert_eq!(quantity.unit, Some(Unit::Pinch));
+    assert_eq!(text, "a pinch of");
+    assert_eq!(rest, "salt");
+
+    assert!(Quantity::split_prefix("a little salt").is_none());
+  }
+
+  #[test]
+  fn plural_units() {
+    assert_eq!(Unit::from_word("cups"), Some(Unit::Cup));
+    assert_eq!(Unit::from_word("Tablespoons"), Some(Unit::Tablespoon));
+    assert_eq!(Unit::from_word("lbs."), Some(Unit::Pound));
+    assert_eq!(Unit::from_word("T"), Some(Unit::Tablespoon));
+    assert_eq!(Unit::from_word("t"), Some(Unit::Teaspoon));
+  }
+
+  #[test]
+  fn package_sizes() {
+    let (quantity, text, rest) = split("1 (15 oz) can black beans");
+    assert_eq!(quantity.amount, 1.0);
+    assert_eq!(quantity.unit, Some(Unit::Can));
+    assert_eq!(text, "1 (15 oz) can");
+    assert_eq!(rest, "black beans");
+    assert_eq!(quantity.package_size, Some((15.0, Unit::Ounce)));
+    assert_eq!(quantity.to_string(), "1 (15 oz) can");
+  }
+
+  #[test]
+  fn package_measures() {
+    let conversions = Conversions {
+      grams_per_each: Some(50.0),
+      grams_per_portion: HashMap::from([(Unit::Clove, 5.0)]),
+      ..Default::default()
+    };
+    let grams = |input: &str| Quantity::parse(input).unwrap().measure().to_grams(&conversions);
+
+    assert_eq!(grams("2 (100 g) cans"), Some(200.0));
+    assert_eq!(grams("3 cloves"), Some(15.0));
+    assert_eq!(grams("1 can"), None);
+    assert_eq!(grams("2"), Some(100.0));
+  }
+
+  #[test]
+  fn no_unit() {
+    let (quantity, text, rest) = split("3 eggs");
+    assert_eq!((quantity.amount, quantity.unit), (3.0, None));
+    assert_eq!((text, rest), ("3", "eggs"));
+
+    assert!(Quantity::split_prefix("salt to taste").is_none());
+  }
+
+  #[test]
+  fn display_uses_plural_units() {
+    assert_eq!(Quantity::parse("2 cup").unwrap().to_string(), "2 cups");
+    assert_eq!(Quantity::parse("1 cup").unwrap().to_string(), "1 cup");
+    assert_eq!(Quantity::parse("1-2 pinch").unwrap().to_string(), "1-2 pinches");
+    assert_eq!(Quantity::parse("3 tbsp").unwrap().to_string(), "3 Tbsp");
+  }
+}
@@ -0,0 +1,240 @@
+use itertools::Itertools;
+
+use crate::links::LinkIndex;
+use crate::models::{Recipe, RecipeStep};
+use crate::name_match::normalize;
+use crate::steps::{find_durations, format_duration};
+
+/// How long to plan for steps that don't say how long they take
+const DEFAULT_STEP_SECONDS: i32 = 5 * 60;
+
+/// Steps can be left alone when their time goes with one of these words, like "bake 20 minutes"
+const HANDS_OFF_WORDS: &[&str] = &[
+  "bake",
+  "baking",
+  "boil",
+  "boiling",
+  "braise",
+  "braising",
+  "chill",
+  "chilling",
+  "cool",
+  "cooling",
+  "freeze",
+  "marinate",
+  "preheat",
+  "proof",
+  "proofing",
+  "refrigerate",
+  "rest",
+  "resting",
+  "rise",
+  "rising",
+  "roast",
+  "roasting",
+  "simmer",
+  "simmering",
+  "soak",
+  "soaking",
+  "steep",
+  "steeping",
+];
+
+const SECONDS_PER_DAY: i32 = 24 * 60 * 60;
+
+/// Timeline of steps from several recipes, worked back from when the food is served
+pub struct Schedule {
+  pub serve_at: i32, // Seconds after midnight
+  pub steps: Vec<ScheduledStep>,
+}
+
+pub struct ScheduledStep {
+  pub recipe_name: String,
+  pub step_number: i32,
+  pub text_markdown: String,
+  pub start: i32, // Seconds after midnight, or negative for the day before
+  pub seconds: i32,
+  pub estimated: bool,
+  pub hands_on: bool,
+  pub overlaps: Vec<String>, // Other hands-on steps at the same time, like "Bread step 2"
+}
+
+impl Schedule {
+  /// Each recipe's steps run back to back so that every recipe is done at the serve time
+  pub fn new(serve_at: i32, recipes: Vec<(Recipe, Vec<RecipeStep>)>) -> Self {
+    let mut steps = Vec::new();
+    for (recipe, recipe_steps) in recipes {
+      let mut end = serve_at;
+      for step in recipe_steps.into_iter().rev() {
+        let seconds = step.duration_seconds.filter(|s| *s > 0).unwrap_or(DEFAULT_STEP_SECONDS);
+        steps.push(ScheduledStep {
+          recipe_name: recipe.name.clone(),
+          step_number: step.step_number,
+          start: end.saturating_sub(seconds),
+          seconds,
+          estimated: step.duration_seconds.is_none(),
+          hands_on: !is_hands_off(&step.text_markdown, step.duration_seconds),
+          overlaps: Vec::new(),
+          text_markdown: step.text_markdown,
+        });
+        end = end.saturating_sub(seconds);
+      }
+    }
+
+    // Stable sort, so steps starting together stay in the order the recipes were given
+    steps.sort_by_key(|step| step.start);
+
+    // Nobody can knead dough and chop onions at the same time
+    for i in 0..steps.len() {
+      let overlaps = steps
+        .iter()
+        .filter(|other| {
+          steps[i].hands_on
+            && other.hands_on
+            && other.recipe_name != steps[i].recipe_name
+            && other.start < steps[i].end()
+            && steps[i].start < other.end()
+        })
+        .map(|other| format!("{} step {}", other.recipe_name, other.step_number))
+        .collect_vec();
+      steps[i].overlaps = overlaps;
+    }
+
+    Self { serve_at, steps }
+  }
+
+  pub fn has_overlaps(&self) -> bool {
+    self.steps.iter().any(|step| !step.overlaps.is_empty())
+  }
+
+  pub fn markdown_string(&self, links: &LinkIndex) -> String {
+    let mut markdown = format!("# Schedule\nServe at {}\n\n", format_clock_time(self.serve_at));
+    markdown.push_str("| Start | Recipe | Step | Time | Instruction |\n|:- |:- | -:|:- |:- |\n");
+
+    for step in self.steps.iter() {
+      let mut time = format_duration(step.seconds);
+      if step.estimated {
+        time = format!("~{}", time);
+      }
+      if !step.hands_on {
+        time.push_str(" (hands-off)");
+      }
+
+      let mut instruction = links.resolve_links(&step.text_markdown).replace('\n', " ");
+      if !step.overlaps.is_empty() {
+        instruction = format!(
+          "⚠ {} *(hands-on at the same time as {})*",
+          instruction,
+          step.overlaps.join(", ")
+        );
+      }
+
+      markdown.push_str(&format!(
+        "|{}|{}|{}|{}|{}|\n",
+        format_clock_time(step.start),
+        step.recipe_name,
+        step.step_number,
+        time,
+        instruction
+      ));
+    }
+    markdown.push_str(&format!("|{}| | | |**Serve**|\n", format_clock_time(self.serve_at)));
+
+    if self.has_overlaps() {
+      markdown.push_str("\n⚠ Some hands-on steps overlap, so start those a little earlier or get some help.\n");
+    }
+    markdown
+  }
+}
+
+impl ScheduledStep {
+  fn end(&self) -> i32 {
+    self.start.saturating_add(self.seconds)
+  }
+}
+
+/// True if the step's time belongs to a hands-off word in the same part of the sentence
+///
+/// "Knead for 10 minutes, then let it rest" is hands-on, but "Chop the onions, then simmer 20 minutes" isn't.
+fn is_hands_off(text: &str, duration_seconds: Option<i32>) -> bool {
+  let Some(seconds) = duration_seconds else {
+    return false;
+  };
+
+  text
+    .split([',', ';', '\n'])
+    .flat_map(|clause| clause.split(". "))
+    .filter(|clause| find_durations(clause).iter().any(|d| d.seconds == seconds))
+    .any(|clause| {
+      normalize(clause)
+        .iter()
+        .any(|word| HANDS_OFF_WORDS.contains(&word.as_str()))
+    })
+}
+
+/// Parse a 24-hour time like "18:30" into seconds after midnight
+pub fn parse_clock_time(text: &str) -> Result<i32, String> {
+  let error = || format!("Expected a time like 18:30, got \"{}\"", text);
+  let (hours, minutes) = text.trim().split_once(':').ok_or_else(error)?;
+  let hours: i32 = hours.parse().map_err(|_| error())?;
+  let minutes: i32 = minutes.parse().map_err(|_| error())?;
+  if !(0..24).contains(&hours) || !(0..60).contains(&minutes) {
+    return Err(error());
+  }
+  Ok(hours * 3600 + minutes * 60)
+}
+
+/// Format seconds after midnight like "17:05", rounding down to the minute
+pub fn format_clock_time(seconds: i32) -> String {
+  let days_before = -seconds.div_euclid(SECONDS_PER_DAY);
+  let seconds = seconds.rem_euclid(SECONDS_PER_DAY);
+  let time = format!("{}:{:02}", seconds / 3600, (seconds % 3600) / 60);
+  match days_before {
+    0 => time,
+    1 => format!("{} (day before)", time),
+    days => format!("{} ({} days before)", time, days),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn hands_off_steps() {
+    assert!(is_hands_off("Bake for 20 minutes", Some(20 * 60)));
+    assert!(is_hands_off("Chop the onions, then simmer 20 minutes", Some(20 * 60)));
+    assert!(is_hands_off("Shape the dough. Let rise 1 hour", Some(60 * 60)));
+  }
+
+  #[test]
+  fn hands_on_steps() {
+    assert!(!is_hands_off("Knead for 10 minutes, then let it rest", Some(10 * 60)));
+    assert!(!is_hands_off("Preheat the oven to 350°F", None));
+    assert!(!is_hands_off("Chop the onions", None));
+  }
+
+  #[test]
+  fn long_steps_saturate() {
+    let recipe = Recipe {
+      id: 1,
+      name: "Jerky".into(),
+      instructions_markdown: String::new(),
+      notes_markdown: String::new(),
+      deleted_at: None,
+    };
+    let step = |step_number| RecipeStep {
+      id: step_number,
+      recipe_id: 1,
+      step_number,
+      text_markdown: "Dry for a very long time".into(),
+      duration_seconds: Some(i32::MAX),
+      temperature: None,
+    };
+
+    let schedule = Schedule::new(18 * 3600, vec![(recipe, vec![step(1), step(2)])]);
+    assert_eq!(schedule.steps[0].start, i32::MIN);
+    assert_eq!(schedule.steps[1].start, 18 * 3600 - i32::MAX);
+    schedule.markdown_string(&LinkIndex::new(Vec::new(), crate::links::LinkStyle::Terminal));
+  }
+}
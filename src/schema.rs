@@ -1,9 +1,93 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    food_portions (food_id, unit) {
+        food_id -> Integer,
+        unit -> Text,
+        grams -> Double,
+    }
+}
+
+diesel::table! {
+    foods (id) {
+        id -> Integer,
+        description -> Text,
+        calories -> Nullable<Double>,
+        protein_grams -> Nullable<Double>,
+        fat_grams -> Nullable<Double>,
+        carbohydrate_grams -> Nullable<Double>,
+        grams_per_ml -> Nullable<Double>,
+        grams_per_each -> Nullable<Double>,
+    }
+}
+
+diesel::table! {
+    ingredient_aliases (name) {
+        name -> Text,
+        ingredient_id -> Integer,
+    }
+}
+
+diesel::table! {
+    ingredient_categories (id) {
+        id -> Text,
+        name -> Text,
+        aisle_order -> Integer,
+    }
+}
+
+diesel::table! {
+    ingredient_flags (ingredient_id, flag) {
+        ingredient_id -> Integer,
+        flag -> Text,
+    }
+}
+
+diesel::table! {
+    ingredient_foods (ingredient_id) {
+        ingredient_id -> Integer,
+        food_id -> Integer,
+        grams_per_ml -> Nullable<Double>,
+        grams_per_each -> Nullable<Double>,
+    }
+}
+
+diesel::table! {
+    ingredient_prices (id) {
+        id -> Integer,
+        ingredient_id -> Integer,
+        price_cents -> Integer,
+        package_quantity -> Text,
+        store -> Nullable<Text>,
+        recorded_on -> Text,
+    }
+}
+
+diesel::table! {
+    ingredient_substitutions (id) {
+        id -> Integer,
+        ingredient_id -> Integer,
+        quantity -> Text,
+        notes_markdown -> Text,
+    }
+}
+
 diesel::table! {
     ingredients (id) {
         id -> Integer,
         name -> Text,
+        category_id -> Nullable<Text>,
+        flags_reviewed -> Bool,
+    }
+}
+
+diesel::table! {
+    recipe_drafts (id) {
+        id -> Integer,
+        recipe_id -> Nullable<Integer>,
+        updated_at -> Text,
+        name -> Text,
+        editor_state -> Text,
     }
 }
 
@@ -17,6 +101,61 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    recipe_revision_ingredients (recipe_revision_id, ingredient_id) {
+        recipe_revision_id -> Integer,
+        ingredient_id -> Integer,
+        display_order -> Integer,
+        quantity -> Text,
+        notes_markdown -> Text,
+    }
+}
+
+diesel::table! {
+    recipe_relations (recipe_id, related_recipe_id, kind) {
+        recipe_id -> Integer,
+        related_recipe_id -> Integer,
+        kind -> Text,
+    }
+}
+
+diesel::table! {
+    recipe_revision_tags (recipe_revision_id, tag_id) {
+        recipe_revision_id -> Integer,
+        tag_id -> Text,
+    }
+}
+
+diesel::table! {
+    recipe_revisions (id) {
+        id -> Integer,
+        recipe_id -> Integer,
+        revision -> Integer,
+        created_at -> Text,
+        name -> Text,
+        instructions_markdown -> Text,
+        notes_markdown -> Text,
+    }
+}
+
+diesel::table! {
+    recipe_step_ingredients (step_id, ingredient_id) {
+        step_id -> Integer,
+        ingredient_id -> Integer,
+    }
+}
+
+diesel::table! {
+    recipe_steps (id) {
+        id -> Integer,
+        recipe_id -> Integer,
+        step_number -> Integer,
+        text_markdown -> Text,
+        duration_seconds -> Nullable<Integer>,
+        temperature -> Nullable<Text>,
+    }
+}
+
 diesel::table! {
     recipe_tags (recipe_id, tag_id) {
         recipe_id -> Integer,
@@ -24,12 +163,39 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    recipe_template_tags (template_id, tag_id) {
+        template_id -> Text,
+        tag_id -> Text,
+    }
+}
+
+diesel::table! {
+    recipe_templates (id) {
+        id -> Text,
+        name -> Text,
+        ingredients_text -> Text,
+        instructions_markdown -> Text,
+        notes_markdown -> Text,
+    }
+}
+
 diesel::table! {
     recipes (id) {
         id -> Integer,
         name -> Text,
         instructions_markdown -> Text,
         notes_markdown -> Text,
+        deleted_at -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    substitution_components (substitution_id, ingredient_id) {
+        substitution_id -> Integer,
+        ingredient_id -> Integer,
+        display_order -> Integer,
+        quantity -> Text,
     }
 }
 
@@ -41,15 +207,54 @@ diesel::table! {
     }
 }
 
+diesel::joinable!(food_portions -> foods (food_id));
+diesel::joinable!(ingredient_aliases -> ingredients (ingredient_id));
+diesel::joinable!(ingredient_flags -> ingredients (ingredient_id));
+diesel::joinable!(ingredient_foods -> foods (food_id));
+diesel::joinable!(ingredient_foods -> ingredients (ingredient_id));
+diesel::joinable!(ingredient_prices -> ingredients (ingredient_id));
+diesel::joinable!(ingredient_substitutions -> ingredients (ingredient_id));
+diesel::joinable!(ingredients -> ingredient_categories (category_id));
+diesel::joinable!(recipe_drafts -> recipes (recipe_id));
 diesel::joinable!(recipe_ingredients -> ingredients (ingredient_id));
 diesel::joinable!(recipe_ingredients -> recipes (recipe_id));
+diesel::joinable!(recipe_revision_ingredients -> ingredients (ingredient_id));
+diesel::joinable!(recipe_revision_ingredients -> recipe_revisions (recipe_revision_id));
+diesel::joinable!(recipe_revision_tags -> recipe_revisions (recipe_revision_id));
+diesel::joinable!(recipe_revision_tags -> tags (tag_id));
+diesel::joinable!(recipe_revisions -> recipes (recipe_id));
+diesel::joinable!(recipe_step_ingredients -> ingredients (ingredient_id));
+diesel::joinable!(recipe_step_ingredients -> recipe_steps (step_id));
+diesel::joinable!(recipe_steps -> recipes (recipe_id));
 diesel::joinable!(recipe_tags -> recipes (recipe_id));
 diesel::joinable!(recipe_tags -> tags (tag_id));
+diesel::joinable!(recipe_template_tags -> recipe_templates (template_id));
+diesel::joinable!(recipe_template_tags -> tags (tag_id));
+diesel::joinable!(substitution_components -> ingredient_substitutions (substitution_id));
+diesel::joinable!(substitution_components -> ingredients (ingredient_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    food_portions,
+    foods,
+    ingredient_aliases,
+    ingredient_categories,
+    ingredient_flags,
+    ingredient_foods,
+    ingredient_prices,
+    ingredient_substitutions,
     ingredients,
+    recipe_drafts,
     recipe_ingredients,
+    recipe_relations,
+    recipe_revision_ingredients,
+    recipe_revision_tags,
+    recipe_revisions,
+    recipe_step_ingredients,
+    recipe_steps,
     recipe_tags,
+    recipe_template_tags,
+    recipe_templates,
     recipes,
+    substitution_components,
     tags,
 );
@@ -0,0 +1,314 @@
+/// Step read out of a numbered markdown list
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedStep {
+  pub text_markdown: String,
+  pub duration_seconds: Option<i32>,
+  pub temperature: Option<String>,
+}
+
+/// Length of time mentioned in a step, like "20 minutes" or "1 hour 30 minutes"
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeMention {
+  pub text: String,
+  pub seconds: i32, // Upper end of ranges like "20-25 minutes"
+}
+
+/// Split instructions into steps, or None if they aren't written as a numbered list
+///
+/// Indented lines and lines directly after a step belong to that step.
+///   Anything else between the lists, like headings, is skipped.
+pub fn parse_steps(instructions_markdown: &str) -> Option<Vec<ParsedStep>> {
+  let mut steps: Vec<String> = Vec::new();
+  let mut in_step = false;
+
+  for line in instructions_markdown.lines() {
+    if let Some(text) = strip_list_number(line) {
+      steps.push(text.trim().to_string());
+      in_step = true;
+    } else if line.trim().is_empty() {
+      in_step = false;
+    } else if in_step || (line.starts_with([' ', '\t']) && !steps.is_empty()) {
+      let step = steps.last_mut().unwrap();
+      step.push('\n');
+      step.push_str(line.trim());
+      in_step = true;
+    }
+  }
+
+  if steps.is_empty() {
+    return None;
+  }
+
+  Some(
+    steps
+      .into_iter()
+      .map(|text_markdown| {
+        // Other times are usually part of the longest one, like "simmer 30 minutes, stirring every 5 minutes"
+        ParsedStep {
+          duration_seconds: find_durations(&text_markdown).iter().map(|d| d.seconds).max(),
+          temperature: find_temperature(&text_markdown),
+          text_markdown,
+        }
+      })
+      .collect(),
+  )
+}
+
+/// Every length of time in the text, in order
+pub fn find_durations(text: &str) -> Vec<TimeMention> {
+  let words = split_words(text);
+  let mut mentions: Vec<TimeMention> = Vec::new();
+  let mut last_end: Option<usize> = None;
+
+  let mut i = 0;
+  while i < words.len() {
+    let Some((seconds, next)) = parse_duration(&words, i) else {
+      i += 1;
+      continue;
+    };
+
+    // "1 hour 30 minutes" and "1 hour and 30 minutes" are a single timer
+    let joined = match (mentions.last_mut(), last_end) {
+      (Some(previous), Some(end)) if end == i || (end + 1 == i && words[end].eq_ignore_ascii_case("and")) => {
+        previous.text = format!("{} {}", previous.text, words[end..next].join(" "));
+        previous.seconds = previous.seconds.saturating_add(seconds);
+        true
+      },
+      _ => false,
+    };
+    if !joined {
+      mentions.push(TimeMention {
+        text: words[i..next].join(" "),
+        seconds,
+      });
+    }
+
+    last_end = Some(next);
+    i = next;
+  }
+
+  mentions
+}
+
+/// First oven or stove temperature in the text, like "350°F"
+pub fn find_temperature(text: &str) -> Option<String> {
+  let words = split_words(text);
+  for (i, word) in words.iter().enumerate() {
+    let digits_end = word.find(|c: char| !c.is_ascii_digit()).unwrap_or(word.len());
+    if digits_end == 0 {
+      continue;
+    }
+    let (number, suffix) = word.split_at(digits_end);
+
+    let next_word = words.get(i + 1).map(|w| w.to_lowercase()).unwrap_or_default();
+    let scale_after = |index: usize| {
+      let scale = words.get(index).map(|w| w.to_lowercase()).unwrap_or_default();
+      match scale.trim_start_matches('°') {
+        "f" | "fahrenheit" => Some("F"),
+        "c" | "celsius" => Some("C"),
+        _ => None,
+      }
+    };
+
+    // Only oven temperatures can leave out the degree sign, so "2C" isn't read as a temperature
+    let scale = match suffix.to_lowercase().as_str() {
+      "°f" => Some("F"),
+      "°c" => Some("C"),
+      "f" if number.len() >= 3 => Some("F"),
+      "c" if number.len() >= 3 => Some("C"),
+      "°" => scale_after(i + 1).or(Some("")),
+      "" if next_word == "degrees" || next_word == "°" => scale_after(i + 2).or(Some("")),
+      "" => scale_after(i + 1).filter(|_| next_word.starts_with('°')),
+      _ => None,
+    };
+
+    if let Some(scale) = scale {
+      return Some(format!("{}°{}", number, scale));
+    }
+  }
+  None
+}
+
+/// "1. Text" or "1) Text"
+fn strip_list_number(line: &str) -> Option<&str> {
+  let digits_end = line.find(|c: char| !c.is_ascii_digit())?;
+  if digits_end == 0 {
+    return None;
+  }
+  let rest = line[digits_end..].strip_prefix(['.', ')'])?;
+  if !rest.is_empty() && !rest.starts_with([' ', '\t']) {
+    return None;
+  }
+  Some(rest)
+}
+
+/// Words with surrounding punctuation removed, and "20-minute" split into "20" and "minute"
+fn split_words(text: &str) -> Vec<&str> {
+  text
+    .split_whitespace()
+    .flat_map(|word| match word.split_once('-') {
+      Some((number, unit)) if number.parse::<f64>().is_ok() && unit_seconds(unit).is_some() => vec![number, unit],
+      _ => vec![word],
+    })
+    .map(|word| {
+      word
+        .trim_matches(|c: char| "()[],;:!?*_\"'".contains(c))
+        .trim_end_matches('.')
+    })
+    .filter(|word| !word.is_empty())
+    .collect()
+}
+
+/// Parse "20 minutes", "1 1/2 hours", "20-25 minutes", "20 to 25 minutes", or "an hour" starting at the index
+///
+/// Returns the number of seconds and the index after the duration.
+fn parse_duration(words: &[&str], start: usize) -> Option<(i32, usize)> {
+  let mut index = start;
+  let word = words.get(index)?.to_lowercase();
+
+  let amount = match word.as_str() {
+    "a" | "an" | "one" => 1.0,
+    "two" => 2.0,
+    "three" => 3.0,
+    "four" => 4.0,
+    "five" => 5.0,
+    "ten" => 10.0,
+    _ => match word.split_once(['-', '–']) {
+      // Use the upper end of a range, so nothing is undercooked
+      Some((_, max)) => parse_number(max)?,
+      None => parse_number(&word)?,
+    },
+  };
+  index += 1;
+
+  // Mixed numbers like "1 1/2", and ranges like "20 to 25"
+  let mut amount = amount;
+  if let Some(fraction) = words
+    .get(index)
+    .filter(|w| w.contains('/'))
+    .and_then(|w| parse_number(w))
+  {
+    amount += fraction;
+    index += 1;
+  }
+  if let (Some(separator), Some(max)) = (words.get(index), words.get(index + 1)) {
+    if matches!(separator.to_lowercase().as_str(), "to" | "or" | "-" | "–") {
+      if let Some(max) = parse_number(max) {
+        amount = max;
+        index += 2;
+      }
+    }
+  }
+
+  let unit = unit_seconds(words.get(index)?)?;
+  Some(((amount * unit as f64).round() as i32, index + 1))
+}
+
+fn unit_seconds(word: &str) -> Option<i32> {
+  match word.to_lowercase().as_str() {
+    "second" | "seconds" | "sec" | "secs" => Some(1),
+    "minute" | "minutes" | "min" | "mins" => Some(60),
+    "hour" | "hours" | "hr" | "hrs" => Some(60 * 60),
+    _ => None,
+  }
+}
+
+fn parse_number(word: &str) -> Option<f64> {
+  match word.split_once('/') {
+    Some((numerator, denominator)) => {
+      let denominator: f64 = denominator.parse().ok()?;
+      (denominator != 0.0).then_some(numerator.parse::<f64>().ok()? / denominator)
+    },
+    None => word
+      .parse()
+      .ok()
+      .filter(|number: &f64| number.is_finite() && *number >= 0.0),
+  }
+}
+
+/// Format seconds like "1 hr 5 min" or "30 sec"
+pub fn format_duration(seconds: i32) -> String {
+  let hours = seconds / 3600;
+  let minutes = (seconds % 3600) / 60;
+  let seconds = seconds % 60;
+
+  let parts: Vec<String> = [(hours, "hr"), (minutes, "min"), (seconds, "sec")]
+    .into_iter()
+    .filter(|(amount, _)| *amount > 0)
+    .map(|(amount, unit)| format!("{} {}", amount, unit))
+    .collect();
+
+  if parts.is_empty() {
+    "0 min".into()
+  } else {
+    parts.join(" ")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn durations(text: &str) -> Vec<(String, i32)> {
+    find_durations(text).into_iter().map(|d| (d.text, d.seconds)).collect()
+  }
+
+  #[test]
+  fn durations_in_text() {
+    assert_eq!(durations("Bake for 20 minutes."), vec![("20 minutes".into(), 20 * 60)]);
+    assert_eq!(durations("Bake 20-25 minutes"), vec![("20-25 minutes".into(), 25 * 60)]);
+    assert_eq!(durations("Bake 20 to 25 min"), vec![("20 to 25 min".into(), 25 * 60)]);
+    assert_eq!(durations("Let rise 1 1/2 hours"), vec![("1 1/2 hours".into(), 90 * 60)]);
+    assert_eq!(durations("Rest for an hour"), vec![("an hour".into(), 60 * 60)]);
+    assert_eq!(durations("a 10-minute rest"), vec![("10 minute".into(), 10 * 60)]);
+    assert_eq!(durations("Add 2 cups of flour"), vec![]);
+  }
+
+  #[test]
+  fn joined_durations() {
+    assert_eq!(
+      durations("Roast 1 hour 30 minutes"),
+      vec![("1 hour 30 minutes".into(), 90 * 60)]
+    );
+    assert_eq!(
+      durations("Roast 1 hour and 15 minutes"),
+      vec![("1 hour and 15 minutes".into(), 75 * 60)]
+    );
+    assert_eq!(
+      durations("Simmer 30 minutes, stirring every 5 minutes"),
+      vec![("30 minutes".into(), 30 * 60), ("5 minutes".into(), 5 * 60)]
+    );
+    assert_eq!(
+      durations("Roast 9999999999 hours 1 minute"),
+      vec![("9999999999 hours 1 minute".into(), i32::MAX)]
+    );
+  }
+
+  #[test]
+  fn step_duration_is_the_longest_time() {
+    let steps = parse_steps("1. Simmer 30 minutes, stirring every 5 minutes\n2. Serve").unwrap();
+    assert_eq!(steps[0].duration_seconds, Some(30 * 60));
+    assert_eq!(steps[1].duration_seconds, None);
+  }
+
+  #[test]
+  fn temperatures() {
+    assert_eq!(find_temperature("Preheat the oven to 350°F."), Some("350°F".into()));
+    assert_eq!(find_temperature("Bake at 180 °C"), Some("180°C".into()));
+    assert_eq!(find_temperature("Heat to 425 degrees F"), Some("425°F".into()));
+    assert_eq!(find_temperature("Heat to 400 degrees"), Some("400°".into()));
+    assert_eq!(find_temperature("Bake at 350F"), Some("350°F".into()));
+    assert_eq!(find_temperature("Add 2C of milk"), None);
+    assert_eq!(find_temperature("Add 2 cups of milk"), None);
+  }
+
+  #[test]
+  fn list_numbers() {
+    assert_eq!(strip_list_number("1. Mix"), Some(" Mix"));
+    assert_eq!(strip_list_number("12) Mix"), Some(" Mix"));
+    assert_eq!(strip_list_number("3."), Some(""));
+    assert_eq!(strip_list_number("1.5 cups"), None);
+    assert_eq!(strip_list_number("2 eggs"), None);
+    assert_eq!(strip_list_number("Mix"), None);
+  }
+}
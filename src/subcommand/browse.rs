@@ -0,0 +1,13 @@
+use clap::Args;
+use diesel::SqliteConnection;
+
+use crate::ui::RecipeBrowser;
+
+#[derive(Args)]
+pub struct BrowseArgs;
+
+impl BrowseArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    RecipeBrowser::new(conn)?.run(conn)
+  }
+}
@@ -0,0 +1,62 @@
+use clap::Args;
+use diesel::SqliteConnection;
+use std::collections::HashSet;
+
+use crate::models::Recipe;
+use crate::ui::pick_recipe;
+
+#[derive(Args)]
+pub struct CloneArgs {
+  /// ID, name, or slug of the recipe to copy
+  ///
+  /// Opens a searchable list of recipes if not given
+  recipe: Option<String>,
+
+  /// Name of the new recipe, instead of adding "(Variant)" to the original name
+  #[arg(short, long)]
+  name: Option<String>,
+}
+
+impl CloneArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let recipe = match pick_recipe(self.recipe.as_deref(), conn)? {
+      None => return Ok(()),
+      Some(recipe) => recipe,
+    };
+
+    let name = match self.name.as_deref().map(str::trim) {
+      Some("") => {
+        println!("Recipe name can't be empty");
+        return Ok(());
+      },
+      Some(name) => name.to_string(),
+      None => default_variant_name(&recipe.name, conn)?,
+    };
+
+    let variant = recipe.create_variant(&name, conn)?;
+    println!(
+      "Created {} (ID: {}) as a variant of {}",
+      variant.name, variant.id, recipe.name
+    );
+
+    Ok(())
+  }
+}
+
+/// "Name (Variant)", or "Name (Variant 2)" and so on if that is taken, including by recipes in the trash
+fn default_variant_name(name: &str, conn: &mut SqliteConnection) -> super::Result<String> {
+  let taken_names: HashSet<String> = Recipe::all(conn)?
+    .into_iter()
+    .chain(Recipe::all_deleted(conn)?)
+    .map(|recipe| recipe.name.to_lowercase())
+    .collect();
+
+  let variant_name = (1..)
+    .map(|number| match number {
+      1 => format!("{} (Variant)", name),
+      number => format!("{} (Variant {})", name, number),
+    })
+    .find(|variant_name| !taken_names.contains(&variant_name.to_lowercase()))
+    .unwrap(); // Will not panic, there are only so many recipes
+  Ok(variant_name)
+}
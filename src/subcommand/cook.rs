@@ -0,0 +1,29 @@
+use clap::Args;
+use diesel::SqliteConnection;
+
+use crate::ui::{pick_recipe, CookMode};
+
+#[derive(Args)]
+pub struct CookArgs {
+  /// ID, name, or slug of the recipe to cook
+  ///
+  /// Opens a searchable list of recipes if not given
+  recipe: Option<String>,
+}
+
+impl CookArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let recipe = match pick_recipe(self.recipe.as_deref(), conn)? {
+      None => return Ok(()),
+      Some(recipe) => recipe,
+    };
+
+    match CookMode::new(&recipe, conn)? {
+      Some(cook_mode) => cook_mode.run(),
+      None => {
+        println!("No numbered steps in the instructions of recipe: {}", recipe.name);
+        Ok(())
+      },
+    }
+  }
+}
@@ -0,0 +1,80 @@
+use clap::Args;
+use diesel::SqliteConnection;
+use itertools::Itertools;
+use termimad::MadSkin;
+
+use crate::models::format_cents;
+use crate::ui::pick_recipe;
+
+#[derive(Args)]
+pub struct CostArgs {
+  /// ID, name, or slug of the recipe
+  recipe: String,
+
+  /// Number of servings the recipe makes, to also show the cost per serving
+  #[arg(short, long)]
+  servings: Option<u32>,
+}
+
+impl CostArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let recipe = match pick_recipe(Some(&self.recipe), conn)? {
+      None => return Ok(()),
+      Some(recipe) => recipe,
+    };
+
+    let estimate = recipe.get_cost_estimate(conn)?;
+    let total = estimate.total_cents();
+
+    let table_header = "| Ingredient | Quantity | Price Used | Cost |\n|:- |:- |:- | -:|";
+    let table_body = estimate
+      .lines
+      .iter()
+      .map(|line| {
+        let store = line
+          .price
+          .store
+          .as_deref()
+          .map(|s| format!(", {}", s))
+          .unwrap_or_default();
+        format!(
+          "|{}|{}|{} for {} ({}{})|{}|",
+          line.ingredient,
+          line.quantity,
+          format_cents(line.price.price_cents as f64),
+          line.price.package_quantity,
+          line.price.recorded_on,
+          store,
+          format_cents(line.cost_cents)
+        )
+      })
+      .join("\n");
+    let table_footer = format!("|**Total**|||**{}**|", format_cents(total));
+
+    let mut markdown = format!(
+      "# Cost: {}\n{}\n{}\n{}",
+      recipe.name, table_header, table_body, table_footer
+    );
+    if let Some(servings) = self.servings.filter(|servings| *servings > 0) {
+      markdown += &format!(
+        "\n**Per serving ({}):** {}",
+        servings,
+        format_cents(total / servings as f64)
+      );
+    }
+
+    let skin = MadSkin::default();
+    skin.print_text(&markdown);
+
+    if !estimate.gaps.is_empty() {
+      let gaps = estimate
+        .gaps
+        .iter()
+        .map(|gap| format!("* **{}**: {}", gap.ingredient, gap.reason))
+        .join("\n");
+      skin.print_text(&format!("Not counted in the total:\n{}", gaps));
+    }
+
+    Ok(())
+  }
+}
@@ -1,13 +1,49 @@
 use clap::Args;
 use diesel::SqliteConnection;
+use std::path::PathBuf;
 
-use crate::ui::RecipeEditor;
+use crate::ui::{pick_template, NewIngredients, RecipeDocument, RecipeEditor};
 
 #[derive(Args)]
-pub struct CreateArgs;
+pub struct CreateArgs {
+  /// Write the whole recipe as a single markdown document
+  #[clap(long, conflicts_with = "from_file")]
+  document: bool,
+
+  /// Create the recipe from a markdown or JSON document without any prompts ("-" reads from stdin)
+  #[clap(long, value_name = "FILE")]
+  from_file: Option<PathBuf>,
+
+  /// What to do with ingredients that aren't in the database when using --from-file
+  #[clap(long, value_enum, default_value_t = NewIngredients::Fail, requires = "from_file")]
+  new_ingredients: NewIngredients,
+
+  /// Start from a recipe template, or pick one from a list if no ID is given
+  #[clap(short, long, value_name = "ID", conflicts_with = "from_file")]
+  template: Option<Option<String>>,
+}
 
 impl CreateArgs {
   pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
-    RecipeEditor::new(conn)?.edit(conn)
+    let editor = RecipeEditor::new(conn)?;
+    if let Some(path) = self.from_file {
+      let document = RecipeDocument::parse_any(&RecipeDocument::read_from(&path)?)?;
+      return editor.import_document(document, self.new_ingredients, conn);
+    }
+
+    // Starting from a template always means a new recipe, so drafts aren't offered
+    let editor = match self.template {
+      None => editor.resume_draft(conn)?,
+      Some(id) => match pick_template(id.as_deref(), conn)? {
+        None => return Ok(()),
+        Some(template) => editor.with_template(&template, conn)?,
+      },
+    };
+
+    if self.document {
+      editor.edit_document(conn)
+    } else {
+      editor.edit(conn)
+    }
   }
 }
@@ -2,28 +2,42 @@ use clap::Args;
 use diesel::SqliteConnection;
 use inquire::Confirm;
 
-use crate::models::Recipe;
+use crate::ui::pick_recipe;
 
 #[derive(Args)]
 pub struct DeleteArgs {
-  /// Integer ID of the recipe to delete
-  id: i32,
+  /// ID, name, or slug of the recipe to delete
+  ///
+  /// Opens a searchable list of recipes if not given
+  recipe: Option<String>,
 
   /// Skip the yes/no prompt
-  #[clap(short, long)]
+  #[clap(long)]
   force: bool,
+
+  /// Permanently delete the recipe instead of moving it to the trash
+  #[clap(long)]
+  purge: bool,
 }
 
 impl DeleteArgs {
   pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
-    match Recipe::find_optional(&self.id, conn)? {
-      None => println!("No such recipe: {}", self.id),
-      Some(recipe) => {
-        if self.force || Confirm::new(&format!("Really delete recipe \"{}\" (Y/N):", recipe.name)).prompt()? {
-          recipe.delete(conn)?;
-          println!("Deleted recipe: {}", recipe.id);
-        }
-      },
+    let recipe = match pick_recipe(self.recipe.as_deref(), conn)? {
+      None => return Ok(()),
+      Some(recipe) => recipe,
+    };
+
+    if self.purge {
+      if self.force || Confirm::new(&format!("Permanently delete recipe \"{}\" (Y/N):", recipe.name)).prompt()? {
+        recipe.delete(conn)?;
+        println!("Deleted recipe: {}", recipe.id);
+      }
+      return Ok(());
+    }
+
+    if self.force || Confirm::new(&format!("Move recipe \"{}\" to the trash (Y/N):", recipe.name)).prompt()? {
+      recipe.trash(conn)?;
+      println!("Moved recipe to the trash: {}", recipe.id);
     }
 
     Ok(())
@@ -0,0 +1,67 @@
+use clap::Args;
+use crossterm::style::Stylize;
+use diesel::SqliteConnection;
+use similar::{ChangeTag, TextDiff};
+
+use crate::models::RecipeRevision;
+use crate::ui::pick_recipe;
+
+#[derive(Args)]
+pub struct DiffArgs {
+  /// ID, name, or slug of the recipe
+  recipe: String,
+
+  /// Revision number to compare against the current recipe
+  revision: i32,
+
+  /// Number of unchanged lines to show around each change
+  #[clap(short, long, default_value_t = 3)]
+  context: usize,
+}
+
+impl DiffArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let recipe = match pick_recipe(Some(&self.recipe), conn)? {
+      None => return Ok(()),
+      Some(recipe) => recipe,
+    };
+
+    let revision = match RecipeRevision::find_by_revision(recipe.id, self.revision, conn)? {
+      None => {
+        println!("No such revision for recipe \"{}\": {}", recipe.name, self.revision);
+        return Ok(());
+      },
+      Some(revision) => revision,
+    };
+
+    let old_text = revision.diff_string(conn)?;
+    let new_text = RecipeRevision::recipe_diff_string(&recipe, conn)?;
+
+    let diff = TextDiff::from_lines(&old_text, &new_text);
+    if diff.ratio() == 1.0 {
+      println!("Revision {} is identical to the current recipe", revision.revision);
+      return Ok(());
+    }
+
+    println!(
+      "{}",
+      format!("--- Revision {} ({})", revision.revision, revision.created_at).red()
+    );
+    println!("{}", "+++ Current".green());
+
+    for hunk in diff.unified_diff().context_radius(self.context).iter_hunks() {
+      println!("{}", hunk.header().to_string().cyan());
+      for change in hunk.iter_changes() {
+        let line = change.to_string_lossy();
+        let line = line.trim_end_matches('\n');
+        match change.tag() {
+          ChangeTag::Delete => println!("{}", format!("-{}", line).red()),
+          ChangeTag::Insert => println!("{}", format!("+{}", line).green()),
+          ChangeTag::Equal => println!(" {}", line),
+        }
+      }
+    }
+
+    Ok(())
+  }
+}
@@ -0,0 +1,48 @@
+use clap::Args;
+use diesel::SqliteConnection;
+use itertools::Itertools;
+use termimad::MadSkin;
+
+use crate::links::{LinkIndex, LinkStyle};
+use crate::models::Recipe;
+
+#[derive(Args)]
+pub struct DoctorArgs;
+
+impl DoctorArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let recipes = Recipe::all_ordered(conn)?;
+    let links = LinkIndex::new(recipes.clone(), LinkStyle::Terminal);
+    let deleted_links = LinkIndex::new(Recipe::all_deleted(conn)?, LinkStyle::Terminal);
+
+    let mut problems = Vec::new();
+    for recipe in recipes.iter() {
+      for link in recipe.get_links() {
+        let problem = match links.find(&link.target).len() {
+          1 => continue,
+          0 if !deleted_links.find(&link.target).is_empty() => "Links to a recipe in the trash",
+          0 => "No recipe with that name or ID",
+          _ => "Matches more than one recipe, so use the ID instead",
+        };
+        problems.push((recipe, link.text.to_string(), problem));
+      }
+    }
+
+    if problems.is_empty() {
+      println!("No broken links in {} recipes", recipes.len());
+      return Ok(());
+    }
+
+    let table_header = "| ID | Recipe Name | Link | Problem |\n| -:|:- |:- |:- |";
+    let table_body = problems
+      .into_iter()
+      .map(|(recipe, link, problem)| format!("|{}|{}|`{}`|{}|", recipe.id, recipe.name, link, problem))
+      .join("\n");
+
+    let markdown = format!("# Broken Links\n{}\n{}", table_header, table_body);
+    let skin = MadSkin::default();
+    skin.print_text(&markdown);
+
+    Ok(())
+  }
+}
@@ -0,0 +1,27 @@
+use clap::Args;
+use diesel::SqliteConnection;
+
+use crate::models::RecipeDraft;
+
+#[derive(Args)]
+pub struct DiscardArgs {
+  /// Integer ID of the draft to discard
+  id: i32,
+}
+
+impl DiscardArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let draft = match RecipeDraft::find_optional(&self.id, conn)? {
+      None => {
+        println!("No such draft: {}", self.id);
+        return Ok(());
+      },
+      Some(draft) => draft,
+    };
+
+    draft.delete(conn)?;
+    println!("Discarded draft: {} (ID: {})", draft.name, draft.id);
+
+    Ok(())
+  }
+}
@@ -0,0 +1,36 @@
+use clap::Args;
+use diesel::SqliteConnection;
+use itertools::Itertools;
+use termimad::MadSkin;
+
+use crate::models::RecipeDraft;
+
+#[derive(Args)]
+pub struct ListArgs;
+
+impl ListArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let table_header = "| Draft ID | Recipe ID | Name | Saved At |\n| -:| -:|:- |:- |";
+    let table_body = RecipeDraft::all_ordered(conn)?
+      .into_iter()
+      .map(|draft| {
+        format!(
+          "|{}|{}|{}|{}|",
+          draft.id,
+          draft
+            .recipe_id
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| "(new)".into()),
+          draft.name,
+          draft.updated_at
+        )
+      })
+      .join("\n");
+
+    let markdown = format!("{}\n{}", table_header, table_body);
+    let skin = MadSkin::default();
+    skin.print_text(&markdown);
+
+    Ok(())
+  }
+}
@@ -0,0 +1,26 @@
+mod discard;
+mod list;
+
+use clap::Subcommand;
+use diesel::SqliteConnection;
+
+pub use super::Result;
+
+#[derive(Subcommand)]
+pub enum DraftsSubcommand {
+  /// List all unsaved drafts from the recipe editor
+  List(list::ListArgs),
+
+  /// Delete an unsaved draft
+  Discard(discard::DiscardArgs),
+}
+
+impl DraftsSubcommand {
+  pub fn execute(self, conn: &mut SqliteConnection) -> Result<()> {
+    use DraftsSubcommand::*;
+    match self {
+      List(args) => args.execute(conn),
+      Discard(args) => args.execute(conn),
+    }
+  }
+}
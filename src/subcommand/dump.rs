@@ -6,7 +6,8 @@ use std::io::{self, Write};
 use std::path::PathBuf;
 use std::process::Command;
 
-use crate::models::{Recipe, Tag};
+use crate::links::{LinkIndex, LinkStyle};
+use crate::models::{Recipe, RelationKind, Tag};
 
 const INDEX_FILENAME: &str = "README.md";
 const RECIPES_FOLDER: &str = "recipes";
@@ -31,6 +32,7 @@ struct IndexTemplateInput {
   recipes_folder: String,
   recipes: Vec<RecipeWithFilename>,
   tags: Vec<TagWithRecipes>,
+  menus: Vec<MenuWithRecipes>,
 
   dump_sql_filename: String,
   skip_sql: bool,
@@ -48,6 +50,13 @@ struct TagWithRecipes {
   recipes: Vec<RecipeWithFilename>,
 }
 
+/// Recipe along with the recipes that pair with it or are sides for it
+#[derive(Clone, Gtmpl)]
+struct MenuWithRecipes {
+  recipe: RecipeWithFilename,
+  pairings: Vec<RecipeWithFilename>,
+}
+
 impl DumpArgs {
   pub fn execute(self, conn: &mut SqliteConnection, database_file: &str) -> super::Result<()> {
     // Parent directory
@@ -63,8 +72,9 @@ impl DumpArgs {
     write!(index_file, "{}", index_markdown)?;
 
     // All of the recipe markdown files
+    let links = LinkIndex::load(LinkStyle::Markdown, conn)?;
     for recipe in Recipe::all(conn)? {
-      let recipe_markdown = recipe.markdown_string(conn)?;
+      let recipe_markdown = recipe.markdown_string(&links, conn)?;
 
       let mut recipe_file = self.create_file(&[RECIPES_FOLDER, &recipe.get_filename()])?;
       write!(recipe_file, "{}", recipe_markdown)?;
@@ -121,10 +131,28 @@ impl DumpArgs {
       })
       .collect::<super::Result<_>>()?;
 
+    let mut menus = Vec::new();
+    for recipe in all_recipes.iter() {
+      let pairings = recipe
+        .get_related_recipes(conn)?
+        .into_iter()
+        .filter(|(kind, reverse, _)| *kind == RelationKind::PairsWith || (*kind == RelationKind::SideFor && *reverse))
+        .flat_map(|(_, _, recipes)| recipes)
+        .map(RecipeWithFilename::from)
+        .collect::<Vec<_>>();
+      if !pairings.is_empty() {
+        menus.push(MenuWithRecipes {
+          recipe: recipe.clone().into(),
+          pairings,
+        });
+      }
+    }
+
     let template_data = IndexTemplateInput {
       recipes_folder: RECIPES_FOLDER.into(),
       recipes: all_recipes.into_iter().map(RecipeWithFilename::from).collect(),
       tags,
+      menus,
 
       dump_sql_filename: DUMP_SQL_FILENAME.into(),
       skip_sql: self.skip_sql,
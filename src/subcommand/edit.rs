@@ -1,26 +1,82 @@
 use clap::Args;
 use diesel::SqliteConnection;
+use std::path::PathBuf;
 
-use crate::models::Recipe;
-use crate::ui::RecipeEditor;
+use crate::ui::{pick_recipe, EditorSection, NewIngredients, RecipeDocument, RecipeEditor};
 
 #[derive(Args)]
 pub struct EditArgs {
-  /// Integer ID of the recipe to delete
-  id: i32,
+  /// ID, name, or slug of the recipe to edit
+  ///
+  /// Opens a searchable list of recipes if not given
+  recipe: Option<String>,
+
+  /// Jump straight to editing the name
+  #[clap(long, group = "section")]
+  name: bool,
+
+  /// Jump straight to editing the ingredients
+  #[clap(long, group = "section")]
+  ingredients: bool,
+
+  /// Jump straight to editing the instructions
+  #[clap(long, group = "section")]
+  instructions: bool,
+
+  /// Jump straight to editing the notes
+  #[clap(long, group = "section")]
+  notes: bool,
+
+  /// Jump straight to editing the tags
+  #[clap(long, group = "section")]
+  tags: bool,
+
+  /// Edit the whole recipe as a single markdown document
+  #[clap(long, group = "section")]
+  document: bool,
+
+  /// Replace the recipe with a markdown or JSON document without any prompts ("-" reads from stdin)
+  #[clap(long, value_name = "FILE", group = "section")]
+  from_file: Option<PathBuf>,
+
+  /// What to do with ingredients that aren't in the database when using --from-file
+  #[clap(long, value_enum, default_value_t = NewIngredients::Fail, requires = "from_file")]
+  new_ingredients: NewIngredients,
 }
 
 impl EditArgs {
   pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
-    let recipe = match Recipe::find_optional(&self.id, conn)? {
-      None => {
-        println!("No such recipe: {}", self.id);
-        return Ok(());
-      },
-
+    let recipe = match pick_recipe(self.recipe.as_deref(), conn)? {
+      None => return Ok(()),
       Some(recipe) => recipe,
     };
 
-    RecipeEditor::from_recipe(recipe, conn)?.edit(conn)
+    let editor = RecipeEditor::from_recipe(recipe, conn)?;
+    if let Some(path) = self.from_file.as_deref() {
+      let document = RecipeDocument::parse_any(&RecipeDocument::read_from(path)?)?;
+      return editor.import_document(document, self.new_ingredients, conn);
+    }
+
+    let editor = editor.resume_draft(conn)?;
+    if self.document {
+      return editor.edit_document(conn);
+    }
+
+    match self.get_section() {
+      Some(section) => editor.edit_section(section, conn),
+      None => editor.edit(conn),
+    }
+  }
+
+  fn get_section(&self) -> Option<EditorSection> {
+    [
+      (self.name, EditorSection::Name),
+      (self.ingredients, EditorSection::Ingredients),
+      (self.instructions, EditorSection::Instructions),
+      (self.notes, EditorSection::Notes),
+      (self.tags, EditorSection::Tags),
+    ]
+    .into_iter()
+    .find_map(|(selected, section)| selected.then_some(section))
   }
 }
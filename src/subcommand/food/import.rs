@@ -0,0 +1,169 @@
+use clap::Args;
+use diesel::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::models::{Food, FoodPortion};
+use crate::quantity::{Measure, Unit};
+
+// Nutrient IDs used by FoodData Central
+const ENERGY_KCAL: i32 = 1008;
+const ENERGY_ATWATER_GENERAL: i32 = 2047;
+const ENERGY_ATWATER_SPECIFIC: i32 = 2048;
+const PROTEIN: i32 = 1003;
+const FAT: i32 = 1004;
+const CARBOHYDRATE: i32 = 1005;
+
+/// Portions like "1 large" or "1 each" are the weight of a whole item
+const WHOLE_ITEM_WORDS: &[&str] = &["each", "whole", "item", "piece", "small", "medium", "large", "extra"];
+
+#[derive(Args)]
+pub struct ImportArgs {
+  /// Folder with the extracted CSV files
+  ///
+  /// Reads "food.csv" and "food_nutrient.csv", along with "food_portion.csv"
+  /// and "measure_unit.csv" for volume and per-item weights if they exist
+  directory: PathBuf,
+}
+
+#[derive(Deserialize)]
+struct FoodRow {
+  fdc_id: i32,
+  description: String,
+}
+
+#[derive(Deserialize)]
+struct FoodNutrientRow {
+  fdc_id: i32,
+  nutrient_id: i32,
+  amount: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct FoodPortionRow {
+  fdc_id: i32,
+  amount: Option<f64>,
+  measure_unit_id: Option<i32>,
+  modifier: Option<String>,
+  gram_weight: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct MeasureUnitRow {
+  id: i32,
+  name: String,
+}
+
+impl ImportArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let mut foods: HashMap<i32, Food> = read_csv::<FoodRow>(&self.directory.join("food.csv"))?
+      .into_iter()
+      .map(|row| {
+        let food = Food {
+          id: row.fdc_id,
+          description: row.description,
+          calories: None,
+          protein_grams: None,
+          fat_grams: None,
+          carbohydrate_grams: None,
+          grams_per_ml: None,
+          grams_per_each: None,
+        };
+        (row.fdc_id, food)
+      })
+      .collect();
+
+    for row in read_csv::<FoodNutrientRow>(&self.directory.join("food_nutrient.csv"))? {
+      let Some(food) = foods.get_mut(&row.fdc_id) else {
+        continue;
+      };
+
+      // Prefer the measured energy, but fall back to the values computed with Atwater factors
+      match row.nutrient_id {
+        ENERGY_KCAL => food.calories = row.amount.or(food.calories),
+        ENERGY_ATWATER_GENERAL | ENERGY_ATWATER_SPECIFIC => food.calories = food.calories.or(row.amount),
+        PROTEIN => food.protein_grams = row.amount,
+        FAT => food.fat_grams = row.amount,
+        CARBOHYDRATE => food.carbohydrate_grams = row.amount,
+        _ => {},
+      }
+    }
+
+    let mut portions: HashMap<(i32, Unit), FoodPortion> = HashMap::new();
+    let portions_path = self.directory.join("food_portion.csv");
+    let units_path = self.directory.join("measure_unit.csv");
+    if portions_path.exists() && units_path.exists() {
+      let unit_names: HashMap<i32, String> = read_csv::<MeasureUnitRow>(&units_path)?
+        .into_iter()
+        .map(|row| (row.id, row.name))
+        .collect();
+
+      for row in read_csv::<FoodPortionRow>(&portions_path)? {
+        let (Some(food), Some(amount), Some(grams)) = (foods.get_mut(&row.fdc_id), row.amount, row.gram_weight) else {
+          continue;
+        };
+        if amount <= 0.0 {
+          continue;
+        }
+
+        // Older datasets leave the unit "undetermined" and put it in the modifier instead, like "cup, chopped"
+        let unit_name = row
+          .measure_unit_id
+          .and_then(|id| unit_names.get(&id))
+          .map_or("", |name| name.as_str());
+        let modifier_word = row
+          .modifier
+          .as_deref()
+          .unwrap_or("")
+          .split_whitespace()
+          .next()
+          .unwrap_or("");
+        let unit = Unit::from_word(unit_name).or_else(|| Unit::from_word(modifier_word));
+        let is_whole_item = [unit_name, modifier_word]
+          .iter()
+          .any(|word| WHOLE_ITEM_WORDS.contains(&word.trim_end_matches(',').to_lowercase().as_str()));
+
+        // The first portion of each kind wins, and portions in units that aren't understood are left out
+        match unit.map(|unit| unit.base_measure()) {
+          Some(Measure::Milliliters(ml)) => food.grams_per_ml = food.grams_per_ml.or(Some(grams / (amount * ml))),
+          Some(Measure::Grams(_)) => {},
+          Some(Measure::Count(_)) => food.grams_per_each = food.grams_per_each.or(Some(grams / amount)),
+          Some(Measure::Portion(unit, count)) => {
+            portions.entry((food.id, unit)).or_insert_with(|| FoodPortion {
+              food_id: food.id,
+              unit: unit.name(false).to_string(),
+              grams: grams / (amount * count),
+            });
+          },
+          None if is_whole_item => food.grams_per_each = food.grams_per_each.or(Some(grams / amount)),
+          None => {},
+        }
+      }
+    }
+
+    conn.transaction(|conn| -> QueryResult<()> {
+      for food in foods.values() {
+        food.insert_or_update(conn)?;
+      }
+      for portion in portions.values() {
+        portion.insert_or_update(conn)?;
+      }
+      Ok(())
+    })?;
+
+    println!("Imported {} foods", foods.len());
+
+    Ok(())
+  }
+}
+
+fn read_csv<T: DeserializeOwned>(path: &Path) -> super::Result<Vec<T>> {
+  let mut reader = csv::Reader::from_path(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+  let rows = reader
+    .deserialize()
+    .collect::<Result<Vec<T>, _>>()
+    .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+  Ok(rows)
+}
@@ -0,0 +1,26 @@
+mod import;
+mod search;
+
+use clap::Subcommand;
+use diesel::SqliteConnection;
+
+pub use super::Result;
+
+#[derive(Subcommand)]
+pub enum FoodSubcommand {
+  /// Import foods from USDA FoodData Central CSV files
+  Import(import::ImportArgs),
+
+  /// Search imported foods by description
+  Search(search::SearchArgs),
+}
+
+impl FoodSubcommand {
+  pub fn execute(self, conn: &mut SqliteConnection) -> Result<()> {
+    use FoodSubcommand::*;
+    match self {
+      Import(args) => args.execute(conn),
+      Search(args) => args.execute(conn),
+    }
+  }
+}
@@ -0,0 +1,53 @@
+use clap::Args;
+use diesel::prelude::*;
+use itertools::Itertools;
+use termimad::MadSkin;
+
+use crate::models::Food;
+use crate::name_match::similarity;
+use crate::schema::foods;
+
+#[derive(Args)]
+pub struct SearchArgs {
+  /// Words that must all appear in the description
+  #[arg(required = true)]
+  words: Vec<String>,
+
+  /// Maximum number of foods to show
+  #[arg(short, long, default_value_t = 20)]
+  limit: usize,
+}
+
+impl SearchArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let search = self.words.join(" ");
+    let mut query = foods::table.into_boxed();
+    for word in search.split_whitespace() {
+      query = query.filter(foods::description.like(format!("%{}%", word)));
+    }
+    let matches = query.get_results::<Food>(conn)?;
+
+    if matches.is_empty() {
+      println!("No foods match: {}", search);
+      return Ok(());
+    }
+
+    let table_header = "| ID | Description | Calories per 100 g |\n| -:|:- | -:|";
+    let table_body = matches
+      .into_iter()
+      .map(|food| (similarity(&search, &food.description), food))
+      .sorted_by(|(a, _), (b, _)| b.total_cmp(a))
+      .take(self.limit)
+      .map(|(_, food)| {
+        let calories = food.calories.map(|c| format!("{:.0}", c)).unwrap_or_default();
+        format!("|{}|{}|{}|", food.id, food.description, calories)
+      })
+      .join("\n");
+
+    let markdown = format!("{}\n{}", table_header, table_body);
+    let skin = MadSkin::default();
+    skin.print_text(&markdown);
+
+    Ok(())
+  }
+}
@@ -0,0 +1,40 @@
+use clap::Args;
+use diesel::SqliteConnection;
+use itertools::Itertools;
+use termimad::MadSkin;
+
+use crate::ui::pick_recipe;
+
+#[derive(Args)]
+pub struct HistoryArgs {
+  /// ID, name, or slug of the recipe
+  recipe: String,
+}
+
+impl HistoryArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let recipe = match pick_recipe(Some(&self.recipe), conn)? {
+      None => return Ok(()),
+      Some(recipe) => recipe,
+    };
+
+    let revisions = recipe.get_recipe_revisions_ordered(conn)?;
+    if revisions.is_empty() {
+      println!("No revisions saved for recipe: {}", recipe.name);
+      return Ok(());
+    }
+
+    let table_header = "| Revision | Saved At | Recipe Name |\n| -:|:- |:- |";
+    let table_body = revisions
+      .into_iter()
+      .map(|revision| format!("|{}|{}|{}|", revision.revision, revision.created_at, revision.name))
+      .join("\n");
+
+    let markdown = format!("{}\n{}", table_header, table_body);
+
+    let skin = MadSkin::default();
+    skin.print_text(&markdown);
+
+    Ok(())
+  }
+}
@@ -0,0 +1,50 @@
+use clap::Args;
+use diesel::SqliteConnection;
+
+use crate::models::{Ingredient, IngredientAlias};
+
+#[derive(Args)]
+pub struct AddArgs {
+  /// ID, name, or alias of the ingredient
+  ingredient: String,
+
+  /// Other name for the ingredient
+  alias: String,
+}
+
+impl AddArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let ingredient = match Ingredient::find_by_reference(&self.ingredient, conn)? {
+      None => {
+        println!("No such ingredient: {}", self.ingredient);
+        return Ok(());
+      },
+      Some(ingredient) => ingredient,
+    };
+
+    let alias = self.alias.trim();
+    if alias.is_empty() {
+      return Err("Alias cannot be empty".into());
+    }
+
+    if let Some(existing) = Ingredient::find_by_reference(alias, conn)? {
+      println!(
+        "Error, \"{}\" already refers to ingredient: {} (ID: {})",
+        alias, existing.name, existing.id
+      );
+      return Ok(());
+    }
+
+    IngredientAlias {
+      name: alias.to_string(),
+      ingredient_id: ingredient.id,
+    }
+    .insert(conn)?;
+    println!(
+      "\"{}\" is now an alias for {} (ID: {})",
+      alias, ingredient.name, ingredient.id
+    );
+
+    Ok(())
+  }
+}
@@ -0,0 +1,42 @@
+use clap::Args;
+use diesel::SqliteConnection;
+use termimad::MadSkin;
+
+use crate::models::{Ingredient, IngredientAlias};
+
+#[derive(Args)]
+pub struct ListArgs {
+  /// Only show aliases for this ingredient (ID, name, or alias)
+  ingredient: Option<String>,
+}
+
+impl ListArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let aliases = match self.ingredient {
+      None => IngredientAlias::all_ordered(conn)?,
+      Some(reference) => match Ingredient::find_by_reference(&reference, conn)? {
+        None => {
+          println!("No such ingredient: {}", reference);
+          return Ok(());
+        },
+        Some(ingredient) => ingredient.get_ingredient_aliases_ordered(conn)?,
+      },
+    };
+
+    let table_header = "| Alias | Ingredient | Ingredient ID |\n|:- |:- | -:|";
+    let table_body = aliases
+      .into_iter()
+      .map(|alias| -> super::Result<_> {
+        let ingredient = alias.get_ingredient(conn)?;
+        Ok(format!("|{}|{}|{}|", alias.name, ingredient.name, ingredient.id))
+      })
+      .collect::<super::Result<Vec<_>>>()?
+      .join("\n");
+
+    let markdown = format!("{}\n{}", table_header, table_body);
+    let skin = MadSkin::default();
+    skin.print_text(&markdown);
+
+    Ok(())
+  }
+}
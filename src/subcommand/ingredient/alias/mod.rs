@@ -0,0 +1,31 @@
+mod add;
+mod list;
+mod remove;
+
+use clap::Subcommand;
+use diesel::SqliteConnection;
+
+pub use super::Result;
+
+#[derive(Subcommand)]
+pub enum AliasSubcommand {
+  /// List ingredient aliases
+  List(list::ListArgs),
+
+  /// Add another name for an ingredient
+  Add(add::AddArgs),
+
+  /// Remove an alias
+  Remove(remove::RemoveArgs),
+}
+
+impl AliasSubcommand {
+  pub fn execute(self, conn: &mut SqliteConnection) -> Result<()> {
+    use AliasSubcommand::*;
+    match self {
+      List(args) => args.execute(conn),
+      Add(args) => args.execute(conn),
+      Remove(args) => args.execute(conn),
+    }
+  }
+}
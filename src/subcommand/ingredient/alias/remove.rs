@@ -0,0 +1,24 @@
+use clap::Args;
+use diesel::SqliteConnection;
+
+use crate::models::IngredientAlias;
+
+#[derive(Args)]
+pub struct RemoveArgs {
+  /// Alias to remove
+  alias: String,
+}
+
+impl RemoveArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    match IngredientAlias::find_optional(&self.alias, conn)? {
+      None => println!("No such alias: {}", self.alias),
+      Some(alias) => {
+        alias.delete(conn)?;
+        println!("Removed alias: {}", alias.name);
+      },
+    }
+
+    Ok(())
+  }
+}
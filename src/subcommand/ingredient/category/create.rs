@@ -0,0 +1,42 @@
+use clap::Args;
+use diesel::SqliteConnection;
+
+use crate::models::IngredientCategory;
+
+#[derive(Args)]
+pub struct CreateArgs {
+  /// Unique ID associated with the category
+  id: String,
+
+  /// Pretty-print name for the category
+  #[arg(short, long)]
+  name: Option<String>,
+
+  /// Position of the category when walking through the store (defaults to last)
+  #[arg(short, long)]
+  aisle: Option<i32>,
+}
+
+impl CreateArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    if IngredientCategory::exists_from_id(&self.id, conn)? {
+      println!("Error, existing category: {}", self.id);
+      return Ok(());
+    }
+
+    let aisle_order = match self.aisle {
+      Some(aisle) => aisle,
+      None => IngredientCategory::next_aisle_order(conn)?,
+    };
+
+    let category = IngredientCategory {
+      id: self.id.clone(),
+      name: self.name.unwrap_or(self.id),
+      aisle_order,
+    };
+    category.insert(conn)?;
+    println!("Created category: {} (ID: {})", category.name, category.id);
+
+    Ok(())
+  }
+}
@@ -0,0 +1,21 @@
+use clap::Args;
+use diesel::SqliteConnection;
+
+use crate::models::IngredientCategory;
+
+#[derive(Args)]
+pub struct DeleteArgs {
+  /// ID for the category
+  id: String,
+}
+
+impl DeleteArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    match IngredientCategory::find_optional(&self.id, conn)? {
+      None => println!("No such category: {}", self.id),
+      Some(category) => category.delete_and_clear(conn)?,
+    }
+
+    Ok(())
+  }
+}
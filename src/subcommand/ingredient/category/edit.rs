@@ -0,0 +1,40 @@
+use clap::Args;
+use diesel::SqliteConnection;
+
+use crate::models::IngredientCategory;
+
+#[derive(Args)]
+pub struct EditArgs {
+  /// Unique ID associated with the category
+  id: String,
+
+  /// Pretty-print name for the category
+  #[arg(short, long)]
+  name: Option<String>,
+
+  /// Position of the category when walking through the store
+  #[arg(short, long)]
+  aisle: Option<i32>,
+}
+
+impl EditArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let mut category = match IngredientCategory::find_optional(&self.id, conn)? {
+      Some(category) => category,
+      None => {
+        println!("No such category: {}", self.id);
+        return Ok(());
+      },
+    };
+
+    if let Some(name) = self.name {
+      category.name = name;
+    }
+    if let Some(aisle_order) = self.aisle {
+      category.aisle_order = aisle_order;
+    }
+    category.update(conn)?;
+
+    Ok(())
+  }
+}
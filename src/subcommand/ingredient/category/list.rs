@@ -0,0 +1,25 @@
+use clap::Args;
+use diesel::SqliteConnection;
+use itertools::Itertools;
+use termimad::MadSkin;
+
+use crate::models::IngredientCategory;
+
+#[derive(Args)]
+pub struct ListArgs;
+
+impl ListArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let table_header = "| Aisle | Category ID | Name |\n| -:|:- |:- |";
+    let table_body = IngredientCategory::all_ordered(conn)?
+      .into_iter()
+      .map(|category| format!("|{}|{}|{}|", category.aisle_order, category.id, category.name))
+      .join("\n");
+
+    let markdown = format!("{}\n{}", table_header, table_body);
+    let skin = MadSkin::default();
+    skin.print_text(&markdown);
+
+    Ok(())
+  }
+}
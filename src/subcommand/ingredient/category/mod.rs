@@ -0,0 +1,36 @@
+mod create;
+mod delete;
+mod edit;
+mod list;
+
+use clap::Subcommand;
+use diesel::SqliteConnection;
+
+pub use super::Result;
+
+#[derive(Subcommand)]
+pub enum CategorySubcommand {
+  /// List all categories in aisle order
+  List(list::ListArgs),
+
+  /// Create a new category
+  Create(create::CreateArgs),
+
+  /// Edit category details
+  Edit(edit::EditArgs),
+
+  /// Delete a category, leaving its ingredients uncategorized
+  Delete(delete::DeleteArgs),
+}
+
+impl CategorySubcommand {
+  pub fn execute(self, conn: &mut SqliteConnection) -> Result<()> {
+    use CategorySubcommand::*;
+    match self {
+      List(args) => args.execute(conn),
+      Create(args) => args.execute(conn),
+      Edit(args) => args.execute(conn),
+      Delete(args) => args.execute(conn),
+    }
+  }
+}
@@ -0,0 +1,76 @@
+use clap::Args;
+use diesel::SqliteConnection;
+
+use crate::models::{DietaryFlag, Ingredient, IngredientCategory};
+
+#[derive(Args)]
+pub struct EditArgs {
+  /// ID, name, or alias of the ingredient
+  ingredient: String,
+
+  /// New name for the ingredient
+  #[arg(short, long)]
+  name: Option<String>,
+
+  /// ID of the category for the ingredient
+  #[arg(short, long, conflicts_with = "no_category")]
+  category: Option<String>,
+
+  /// Remove the ingredient from its category
+  #[arg(long)]
+  no_category: bool,
+
+  /// Allergen or dietary property of the ingredient, replacing any existing flags (can be repeated)
+  #[arg(long, conflicts_with = "no_flags")]
+  flag: Vec<DietaryFlag>,
+
+  /// Mark the ingredient as free of every allergen and dietary flag
+  #[arg(long)]
+  no_flags: bool,
+}
+
+impl EditArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let mut ingredient = match Ingredient::find_by_reference(&self.ingredient, conn)? {
+      Some(ingredient) => ingredient,
+      None => {
+        println!("No such ingredient: {}", self.ingredient);
+        return Ok(());
+      },
+    };
+
+    if let Some(name) = self.name.as_deref().map(str::trim) {
+      if name.is_empty() {
+        return Err("Ingredient name cannot be empty".into());
+      }
+
+      match Ingredient::find_by_reference(name, conn)? {
+        Some(existing) if existing.id != ingredient.id => {
+          println!(
+            "Error, \"{}\" already refers to ingredient: {} (ID: {})",
+            name, existing.name, existing.id
+          );
+          return Ok(());
+        },
+        _ => ingredient.name = name.to_string(),
+      }
+    }
+    if let Some(category_id) = self.category {
+      if !IngredientCategory::exists_from_id(&category_id, conn)? {
+        println!("No such category: {}", category_id);
+        return Ok(());
+      }
+      ingredient.category_id = Some(category_id);
+    }
+    if self.no_category {
+      ingredient.category_id = None;
+    }
+    let ingredient = ingredient.update(conn)?;
+
+    if !self.flag.is_empty() || self.no_flags {
+      ingredient.set_dietary_flags(&self.flag, conn)?;
+    }
+
+    Ok(())
+  }
+}
@@ -0,0 +1,60 @@
+use clap::Args;
+use diesel::SqliteConnection;
+
+use crate::models::{Food, Ingredient, IngredientFood};
+use crate::quantity::{Measure, Unit};
+
+#[derive(Args)]
+pub struct LinkArgs {
+  /// ID, name, or alias of the ingredient
+  ingredient: String,
+
+  /// ID of the food from "recipe food search"
+  food_id: i32,
+
+  /// Weight of one cup in grams, used for volume measurements instead of the food's value
+  #[arg(long)]
+  grams_per_cup: Option<f64>,
+
+  /// Weight of one item in grams, used for counted ingredients like "3 eggs" instead of the food's value
+  #[arg(long)]
+  grams_each: Option<f64>,
+}
+
+impl LinkArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let ingredient = match Ingredient::find_by_reference(&self.ingredient, conn)? {
+      Some(ingredient) => ingredient,
+      None => {
+        println!("No such ingredient: {}", self.ingredient);
+        return Ok(());
+      },
+    };
+
+    let food = match Food::find_optional(&self.food_id, conn)? {
+      Some(food) => food,
+      None => {
+        println!("No such food: {}", self.food_id);
+        return Ok(());
+      },
+    };
+
+    let Measure::Milliliters(ml_per_cup) = Unit::Cup.base_measure() else {
+      unreachable!("cups are a volume");
+    };
+
+    IngredientFood {
+      ingredient_id: ingredient.id,
+      food_id: food.id,
+      grams_per_ml: self.grams_per_cup.map(|grams| grams / ml_per_cup),
+      grams_per_each: self.grams_each,
+    }
+    .insert_or_update(conn)?;
+    println!(
+      "Linked {} (ID: {}) to food: {}",
+      ingredient.name, ingredient.id, food.description
+    );
+
+    Ok(())
+  }
+}
@@ -0,0 +1,69 @@
+use clap::Args;
+use diesel::SqliteConnection;
+use itertools::Itertools;
+use termimad::MadSkin;
+
+use crate::models::{Ingredient, IngredientCategory};
+
+#[derive(Args)]
+pub struct ListArgs {
+  /// Only list ingredients in this category
+  #[clap(short, long)]
+  category: Option<String>,
+}
+
+impl ListArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let all_categories = IngredientCategory::all_ordered(conn)?;
+    if let Some(category) = self.category.as_ref() {
+      if !all_categories.iter().any(|c| &c.id == category) {
+        println!("No such category: {}", category);
+        return Ok(());
+      }
+    }
+
+    // Group by category in aisle order, with uncategorized ingredients at the end
+    let all_ingredients = Ingredient::all_ordered(conn)?;
+    let groups = all_categories
+      .iter()
+      .map(|c| (c.name.as_str(), Some(&c.id)))
+      .chain([("Uncategorized", None)])
+      .filter(|(_, id)| self.category.is_none() || *id == self.category.as_ref());
+
+    let mut sections = Vec::new();
+    for (name, category_id) in groups {
+      let ingredients = all_ingredients
+        .iter()
+        .filter(|i| i.category_id.as_ref() == category_id)
+        .map(|i| -> super::Result<_> {
+          let aliases = i.get_ingredient_aliases_ordered(conn)?;
+          let flags = match i.get_dietary_flags(conn)? {
+            flags if !flags.is_empty() => flags.iter().join(", "),
+            _ if i.flags_reviewed => "None".into(),
+            _ => "".into(),
+          };
+          Ok(format!(
+            "|{}|{}|{}|{}|",
+            i.id,
+            i.name,
+            aliases.into_iter().map(|a| a.name).join(", "),
+            flags
+          ))
+        })
+        .collect::<super::Result<Vec<_>>>()?;
+
+      if !ingredients.is_empty() {
+        sections.push(format!(
+          "## {}\n| ID | Ingredient | Aliases | Flags |\n| -:|:- |:- |:- |\n{}",
+          name,
+          ingredients.join("\n")
+        ));
+      }
+    }
+
+    let skin = MadSkin::default();
+    skin.print_text(&sections.join("\n\n"));
+
+    Ok(())
+  }
+}
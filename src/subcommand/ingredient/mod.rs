@@ -0,0 +1,68 @@
+mod alias;
+mod category;
+mod edit;
+mod link;
+mod list;
+mod price;
+mod substitution;
+mod unlink;
+
+use clap::Subcommand;
+use diesel::SqliteConnection;
+
+pub use super::Result;
+
+#[derive(Subcommand)]
+pub enum IngredientSubcommand {
+  /// List all ingredients grouped by category
+  List(list::ListArgs),
+
+  /// Rename an ingredient or change its category
+  Edit(edit::EditArgs),
+
+  /// Link an ingredient to a food from the nutrient database
+  Link(link::LinkArgs),
+
+  /// Remove the link between an ingredient and its food
+  Unlink(unlink::UnlinkArgs),
+
+  /// Manage other names for ingredients
+  Alias {
+    #[clap(subcommand)]
+    subcommand: alias::AliasSubcommand,
+  },
+
+  /// Record ingredient prices for cost estimates
+  Price {
+    #[clap(subcommand)]
+    subcommand: price::PriceSubcommand,
+  },
+
+  /// Manage ingredients that can be used in place of others
+  Substitution {
+    #[clap(subcommand)]
+    subcommand: substitution::SubstitutionSubcommand,
+  },
+
+  /// Manage ingredient categories and their store aisle order
+  Category {
+    #[clap(subcommand)]
+    subcommand: category::CategorySubcommand,
+  },
+}
+
+impl IngredientSubcommand {
+  pub fn execute(self, conn: &mut SqliteConnection) -> Result<()> {
+    use IngredientSubcommand::*;
+    match self {
+      List(args) => args.execute(conn),
+      Edit(args) => args.execute(conn),
+      Link(args) => args.execute(conn),
+      Unlink(args) => args.execute(conn),
+      Alias { subcommand } => subcommand.execute(conn),
+      Price { subcommand } => subcommand.execute(conn),
+      Substitution { subcommand } => subcommand.execute(conn),
+      Category { subcommand } => subcommand.execute(conn),
+    }
+  }
+}
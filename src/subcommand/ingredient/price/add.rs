@@ -0,0 +1,88 @@
+use clap::Args;
+use diesel::SqliteConnection;
+
+use crate::models::{format_cents, Ingredient, IngredientPriceConstructor};
+use crate::quantity::Quantity;
+
+#[derive(Args)]
+pub struct AddArgs {
+  /// ID, name, or alias of the ingredient
+  ingredient: String,
+
+  /// Price of the whole package, like "3.49"
+  price: String,
+
+  /// Size of the package, like "5 lb", "1 gallon", or "12" for a dozen
+  package: String,
+
+  /// Store where the price was seen
+  #[arg(short, long)]
+  store: Option<String>,
+
+  /// Date of the price as YYYY-MM-DD, defaults to today
+  #[arg(long)]
+  date: Option<String>,
+}
+
+impl AddArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let ingredient = match Ingredient::find_by_reference(&self.ingredient, conn)? {
+      Some(ingredient) => ingredient,
+      None => {
+        println!("No such ingredient: {}", self.ingredient);
+        return Ok(());
+      },
+    };
+
+    let price_cents = match self.price.trim().trim_start_matches('$').parse::<f64>() {
+      Ok(price) if price >= 0.0 => (price * 100.0).round() as i32,
+      _ => return Err(format!("Invalid price: {}", self.price).into()),
+    };
+
+    let package = match Quantity::parse(&self.package) {
+      Some(package) if package.amount > 0.0 => package,
+      _ => return Err(format!("Invalid package size: {}", self.package).into()),
+    };
+
+    if let Some(date) = self.date.as_deref() {
+      if !is_valid_date(date) {
+        return Err(format!("Invalid date, expected YYYY-MM-DD: {}", date).into());
+      }
+    }
+
+    let price = IngredientPriceConstructor {
+      ingredient_id: ingredient.id,
+      price_cents,
+      package_quantity: &package.to_string(),
+      store: self.store.as_deref().map(str::trim).filter(|store| !store.is_empty()),
+      recorded_on: self.date.as_deref(),
+    }
+    .insert_ingredient_price(conn)?;
+
+    println!(
+      "Recorded {} for {} of {} on {} (ID: {})",
+      format_cents(price.price_cents as f64),
+      price.package_quantity,
+      ingredient.name,
+      price.recorded_on,
+      price.id
+    );
+
+    Ok(())
+  }
+}
+
+fn is_valid_date(date: &str) -> bool {
+  let parts: Vec<_> = date.split('-').collect();
+  let is_number = |part: &str, len: usize| part.len() == len && part.chars().all(|c| c.is_ascii_digit());
+  match parts[..] {
+    [year, month, day] => {
+      is_number(year, 4)
+        && is_number(month, 2)
+        && is_number(day, 2)
+        && (1..=12).contains(&month.parse::<u32>().unwrap_or(0))
+        && (1..=31).contains(&day.parse::<u32>().unwrap_or(0))
+    },
+    _ => false,
+  }
+}
@@ -0,0 +1,50 @@
+use clap::Args;
+use diesel::SqliteConnection;
+use termimad::MadSkin;
+
+use crate::models::{format_cents, Ingredient, IngredientPrice};
+
+#[derive(Args)]
+pub struct ListArgs {
+  /// Only show prices for this ingredient (ID, name, or alias)
+  ingredient: Option<String>,
+}
+
+impl ListArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let prices = match self.ingredient {
+      None => IngredientPrice::all_ordered(conn)?,
+      Some(reference) => match Ingredient::find_by_reference(&reference, conn)? {
+        None => {
+          println!("No such ingredient: {}", reference);
+          return Ok(());
+        },
+        Some(ingredient) => ingredient.get_ingredient_prices_ordered(conn)?,
+      },
+    };
+
+    let table_header = "| ID | Date | Ingredient | Price | Package | Store |\n| -:|:- |:- | -:|:- |:- |";
+    let table_body = prices
+      .into_iter()
+      .map(|price| -> super::Result<_> {
+        let ingredient = price.get_ingredient(conn)?;
+        Ok(format!(
+          "|{}|{}|{}|{}|{}|{}|",
+          price.id,
+          price.recorded_on,
+          ingredient.name,
+          format_cents(price.price_cents as f64),
+          price.package_quantity,
+          price.store.unwrap_or_default()
+        ))
+      })
+      .collect::<super::Result<Vec<_>>>()?
+      .join("\n");
+
+    let markdown = format!("{}\n{}", table_header, table_body);
+    let skin = MadSkin::default();
+    skin.print_text(&markdown);
+
+    Ok(())
+  }
+}
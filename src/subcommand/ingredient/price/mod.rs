@@ -0,0 +1,31 @@
+mod add;
+mod list;
+mod remove;
+
+use clap::Subcommand;
+use diesel::SqliteConnection;
+
+pub use super::Result;
+
+#[derive(Subcommand)]
+pub enum PriceSubcommand {
+  /// List the price history of ingredients, newest first
+  List(list::ListArgs),
+
+  /// Record the price of a package of an ingredient
+  Add(add::AddArgs),
+
+  /// Remove a recorded price
+  Remove(remove::RemoveArgs),
+}
+
+impl PriceSubcommand {
+  pub fn execute(self, conn: &mut SqliteConnection) -> Result<()> {
+    use PriceSubcommand::*;
+    match self {
+      List(args) => args.execute(conn),
+      Add(args) => args.execute(conn),
+      Remove(args) => args.execute(conn),
+    }
+  }
+}
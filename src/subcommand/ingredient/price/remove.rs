@@ -0,0 +1,24 @@
+use clap::Args;
+use diesel::SqliteConnection;
+
+use crate::models::IngredientPrice;
+
+#[derive(Args)]
+pub struct RemoveArgs {
+  /// ID of the price, from "recipe ingredient price list"
+  id: i32,
+}
+
+impl RemoveArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    match IngredientPrice::find_optional(&self.id, conn)? {
+      None => println!("No such price: {}", self.id),
+      Some(price) => {
+        price.delete(conn)?;
+        println!("Removed price: {}", price.id);
+      },
+    }
+
+    Ok(())
+  }
+}
@@ -0,0 +1,96 @@
+use clap::Args;
+use diesel::prelude::*;
+
+use crate::models::{Ingredient, IngredientSubstitutionConstructor};
+use crate::name_match::AUTO_ACCEPT_SCORE;
+use crate::quantity::Quantity;
+
+#[derive(Args)]
+pub struct AddArgs {
+  /// Substitution written as "<quantity> <ingredient> = <quantity> <ingredient> + ..."
+  ///
+  /// Ingredients can be referred to by name or alias, like "1 cup buttermilk = 1 cup milk + 1 tbsp lemon juice"
+  substitution: String,
+
+  /// Notes about the substitution, like how it changes the texture
+  #[arg(short, long, default_value = "")]
+  notes: String,
+}
+
+impl AddArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let (replaced, components) = match self.substitution.split_once('=') {
+      Some((replaced, components)) => (replaced, components),
+      None => {
+        return Err("Substitution must be written as \"<quantity> <ingredient> = <quantity> <ingredient>\"".into())
+      },
+    };
+
+    let (quantity, ingredient) = match find_amount(replaced, conn)? {
+      Some(amount) => amount,
+      None => return Ok(()),
+    };
+
+    let mut component_ids = Vec::new();
+    for component in components.split('+') {
+      let (quantity, component_ingredient) = match find_amount(component, conn)? {
+        Some(amount) => amount,
+        None => return Ok(()),
+      };
+      if component_ingredient.id == ingredient.id || component_ids.iter().any(|(id, _)| *id == component_ingredient.id)
+      {
+        return Err(format!("Ingredient is listed twice: {}", component_ingredient.name).into());
+      }
+      component_ids.push((component_ingredient.id, quantity));
+    }
+
+    let substitution = conn.transaction(|conn| {
+      let substitution = IngredientSubstitutionConstructor {
+        ingredient_id: ingredient.id,
+        quantity: &quantity,
+        notes_markdown: self.notes.trim(),
+      }
+      .insert_ingredient_substitution(conn)?;
+      substitution.set_components(&component_ids, conn)?;
+      QueryResult::Ok(substitution)
+    })?;
+
+    println!(
+      "Added substitution: {} (ID: {})",
+      substitution.describe(conn)?,
+      substitution.id
+    );
+
+    Ok(())
+  }
+}
+
+/// Split "1 cup buttermilk" into the quantity and the ingredient, printing an error if the ingredient doesn't exist
+fn find_amount(input: &str, conn: &mut SqliteConnection) -> super::Result<Option<(String, Ingredient)>> {
+  let input = input.trim();
+  let (quantity, name) = match Quantity::split_prefix(input) {
+    Some((_, quantity, rest)) if !rest.is_empty() => (quantity, rest),
+    _ => ("", input),
+  };
+  if name.is_empty() {
+    return Err("Ingredient name cannot be empty".into());
+  }
+
+  // Also accept plurals like "2 eggs"
+  let ingredient = match Ingredient::find_by_reference(name, conn)? {
+    Some(ingredient) => Some(ingredient),
+    None => Ingredient::find_similar(name, conn)?
+      .into_iter()
+      .next()
+      .filter(|(_, score)| *score >= AUTO_ACCEPT_SCORE)
+      .map(|(ingredient, _)| ingredient),
+  };
+
+  match ingredient {
+    Some(ingredient) => Ok(Some((quantity.to_string(), ingredient))),
+    None => {
+      println!("No such ingredient: {}", name);
+      Ok(None)
+    },
+  }
+}
@@ -0,0 +1,46 @@
+use clap::Args;
+use diesel::SqliteConnection;
+use termimad::MadSkin;
+
+use crate::models::{Ingredient, IngredientSubstitution};
+
+#[derive(Args)]
+pub struct ListArgs {
+  /// Only show substitutions for this ingredient (ID, name, or alias)
+  ingredient: Option<String>,
+}
+
+impl ListArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let substitutions = match self.ingredient {
+      None => IngredientSubstitution::all_ordered(conn)?,
+      Some(reference) => match Ingredient::find_by_reference(&reference, conn)? {
+        None => {
+          println!("No such ingredient: {}", reference);
+          return Ok(());
+        },
+        Some(ingredient) => ingredient.get_ingredient_substitutions_ordered(conn)?,
+      },
+    };
+
+    let table_header = "| ID | Substitution | Notes |\n| -:|:- |:- |";
+    let table_body = substitutions
+      .into_iter()
+      .map(|substitution| -> super::Result<_> {
+        Ok(format!(
+          "|{}|{}|{}|",
+          substitution.id,
+          substitution.describe(conn)?,
+          substitution.notes_markdown
+        ))
+      })
+      .collect::<super::Result<Vec<_>>>()?
+      .join("\n");
+
+    let markdown = format!("{}\n{}", table_header, table_body);
+    let skin = MadSkin::default();
+    skin.print_text(&markdown);
+
+    Ok(())
+  }
+}
@@ -0,0 +1,31 @@
+mod add;
+mod list;
+mod remove;
+
+use clap::Subcommand;
+use diesel::SqliteConnection;
+
+pub use super::Result;
+
+#[derive(Subcommand)]
+pub enum SubstitutionSubcommand {
+  /// List ingredient substitutions
+  List(list::ListArgs),
+
+  /// Add a substitution like "1 cup buttermilk = 1 cup milk + 1 tbsp lemon juice"
+  Add(add::AddArgs),
+
+  /// Remove a substitution
+  Remove(remove::RemoveArgs),
+}
+
+impl SubstitutionSubcommand {
+  pub fn execute(self, conn: &mut SqliteConnection) -> Result<()> {
+    use SubstitutionSubcommand::*;
+    match self {
+      List(args) => args.execute(conn),
+      Add(args) => args.execute(conn),
+      Remove(args) => args.execute(conn),
+    }
+  }
+}
@@ -0,0 +1,25 @@
+use clap::Args;
+use diesel::SqliteConnection;
+
+use crate::models::IngredientSubstitution;
+
+#[derive(Args)]
+pub struct RemoveArgs {
+  /// ID of the substitution, from "recipe ingredient substitution list"
+  id: i32,
+}
+
+impl RemoveArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    match IngredientSubstitution::find_optional(&self.id, conn)? {
+      None => println!("No such substitution: {}", self.id),
+      Some(substitution) => {
+        let description = substitution.describe(conn)?;
+        substitution.delete(conn)?;
+        println!("Removed substitution: {}", description);
+      },
+    }
+
+    Ok(())
+  }
+}
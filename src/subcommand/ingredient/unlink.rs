@@ -0,0 +1,30 @@
+use clap::Args;
+use diesel::SqliteConnection;
+
+use crate::models::{Ingredient, IngredientFood};
+
+#[derive(Args)]
+pub struct UnlinkArgs {
+  /// ID, name, or alias of the ingredient
+  ingredient: String,
+}
+
+impl UnlinkArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let ingredient = match Ingredient::find_by_reference(&self.ingredient, conn)? {
+      Some(ingredient) => ingredient,
+      None => {
+        println!("No such ingredient: {}", self.ingredient);
+        return Ok(());
+      },
+    };
+
+    if IngredientFood::delete_from_id(&ingredient.id, conn)? == 0 {
+      println!("Ingredient is not linked to a food: {}", ingredient.name);
+      return Ok(());
+    }
+    println!("Unlinked {} (ID: {}) from its food", ingredient.name, ingredient.id);
+
+    Ok(())
+  }
+}
@@ -1,33 +1,87 @@
-use clap::Args;
+use clap::{Args, ValueEnum};
 use diesel::SqliteConnection;
 use itertools::Itertools;
 use termimad::MadSkin;
 
 use crate::models::Recipe;
 use crate::models::Tag;
+use crate::models::{format_cents, Diet, DietaryFlag};
 
 #[derive(Args)]
-pub struct ListArgs;
+pub struct ListArgs {
+  /// Only list recipes that fit the diet (can be repeated)
+  #[clap(long)]
+  diet: Vec<Diet>,
+
+  /// Only list recipes that don't contain the allergen (can be repeated)
+  #[clap(long)]
+  allergen_free: Vec<DietaryFlag>,
+
+  /// Order of the recipes
+  #[clap(long, value_enum, default_value_t = SortOrder::Name)]
+  sort: SortOrder,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum SortOrder {
+  Name,
+
+  /// Cheapest estimated cost first, with unpriced recipes last
+  Cost,
+}
 
 impl ListArgs {
   pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
-    let recipes_with_tags: Vec<(Recipe, Vec<Tag>)> = Recipe::all_ordered(conn)?
-      .into_iter()
-      .map(|r| -> super::Result<_> {
-        let tags = r.get_tags_ordered(conn)?;
-        Ok((r, tags))
-      })
-      .collect::<Result<_, _>>()?;
+    let mut recipes_with_tags: Vec<(Recipe, Vec<Tag>, Option<f64>)> = Vec::new();
+    for recipe in Recipe::all_ordered(conn)? {
+      if !self.diet.is_empty() || !self.allergen_free.is_empty() {
+        let info = recipe.get_dietary_info(conn)?;
+        if !self.diet.iter().all(|diet| info.fits_diet(*diet))
+          || !self.allergen_free.iter().all(|flag| info.is_free_of(*flag))
+        {
+          continue;
+        }
+      }
+
+      let tags = recipe.get_tags_ordered(conn)?;
+
+      // Only fully priced recipes can be compared fairly
+      let cost = match self.sort {
+        SortOrder::Name => None,
+        SortOrder::Cost => {
+          let estimate = recipe.get_cost_estimate(conn)?;
+          estimate.gaps.is_empty().then(|| estimate.total_cents())
+        },
+      };
+
+      recipes_with_tags.push((recipe, tags, cost));
+    }
+
+    if self.sort == SortOrder::Cost {
+      recipes_with_tags.sort_by(|(_, _, a), (_, _, b)| match (a, b) {
+        (Some(a), Some(b)) => a.total_cmp(b),
+        (a, b) => b.is_some().cmp(&a.is_some()),
+      });
+    }
 
-    let table_header = "| ID | Recipe Name | Tags |\n| -:|:- |:- |";
+    let (table_header, cost_column) = match self.sort {
+      SortOrder::Name => ("| ID | Recipe Name | Tags |\n| -:|:- |:- |", false),
+      SortOrder::Cost => ("| ID | Recipe Name | Tags | Cost |\n| -:|:- |:- | -:|", true),
+    };
     let table_body = recipes_with_tags
       .into_iter()
-      .map(|(recipe, tags)| {
+      .map(|(recipe, tags, cost)| {
+        let cost = if cost_column {
+          format!("{}|", cost.map(format_cents).unwrap_or_else(|| "?".into()))
+        } else {
+          "".into()
+        };
         format!(
-          "|{}|{}|{}|",
+          "|{}|{}|{}|{}",
           recipe.id,
           recipe.name,
-          tags.into_iter().map(|t| t.name).join(", ")
+          tags.into_iter().map(|t| t.name).join(", "),
+          cost
         )
       })
       .join("\n");
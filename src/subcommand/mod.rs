@@ -1,10 +1,28 @@
+mod browse;
+mod clone;
+mod cook;
+mod cost;
 mod create;
 mod delete;
+mod diff;
+mod doctor;
+mod drafts;
 mod dump;
 mod edit;
+mod food;
+mod history;
+mod ingredient;
 mod list;
+mod nutrition;
 mod print;
+mod relate;
+mod revert;
+mod schedule;
+mod steps;
+mod suggest;
 mod tag;
+mod template;
+mod trash;
 
 use clap::Subcommand;
 use diesel::SqliteConnection;
@@ -18,26 +36,95 @@ pub enum OptSubcommand {
   /// List all recipes in the database
   List(list::ListArgs),
 
+  /// Full-screen TUI to browse the recipe database
+  Browse(browse::BrowseArgs),
+
   /// TUI to create a new recipe in the database
   Create(create::CreateArgs),
 
   /// TUI to edit a recipe
   Edit(edit::EditArgs),
 
+  /// Copy a recipe with its ingredients and tags, to make a variant of it
+  Clone(clone::CloneArgs),
+
   /// Print out a recipe
   Print(print::PrintArgs),
 
-  /// Delete a recipe
+  /// Mark recipes as pairing with, being a side for, a variation of, or replacing another recipe
+  Relate(relate::RelateArgs),
+
+  /// Move a recipe to the trash
   Delete(delete::DeleteArgs),
 
+  /// List the saved revisions of a recipe
+  History(history::HistoryArgs),
+
+  /// Compare a saved revision with the current recipe
+  Diff(diff::DiffArgs),
+
+  /// Restore a recipe to a saved revision
+  Revert(revert::RevertArgs),
+
   /// Dump the entire database to SQL and markdown files
   Dump(dump::DumpArgs),
 
+  /// Check recipes for links to other recipes that don't work
+  Doctor(doctor::DoctorArgs),
+
+  /// Randomly suggest a recipe to cook
+  Suggest(suggest::SuggestArgs),
+
+  /// Show the instructions of a recipe split into timed steps
+  Steps(steps::StepsArgs),
+
+  /// Full-screen mode that shows one step at a time, with timers
+  Cook(cook::CookArgs),
+
+  /// Plan when to start each step so several recipes are ready at the same time
+  Schedule(schedule::ScheduleArgs),
+
+  /// Calories and macronutrients of a recipe
+  Nutrition(nutrition::NutritionArgs),
+
+  /// Estimate the cost of a recipe from ingredient prices
+  Cost(cost::CostArgs),
+
+  /// Manage ingredients
+  Ingredient {
+    #[clap(subcommand)]
+    subcommand: ingredient::IngredientSubcommand,
+  },
+
+  /// Import and search the nutrient database
+  Food {
+    #[clap(subcommand)]
+    subcommand: food::FoodSubcommand,
+  },
+
   /// Manage tags
   Tag {
     #[clap(subcommand)]
     subcommand: tag::TagSubcommand,
   },
+
+  /// Manage templates for new recipes
+  Template {
+    #[clap(subcommand)]
+    subcommand: template::TemplateSubcommand,
+  },
+
+  /// Manage unsaved drafts from the recipe editor
+  Drafts {
+    #[clap(subcommand)]
+    subcommand: drafts::DraftsSubcommand,
+  },
+
+  /// Manage deleted recipes
+  Trash {
+    #[clap(subcommand)]
+    subcommand: trash::TrashSubcommand,
+  },
 }
 
 impl OptSubcommand {
@@ -45,12 +132,30 @@ impl OptSubcommand {
     use OptSubcommand::*;
     match self {
       List(args) => args.execute(conn),
+      Browse(args) => args.execute(conn),
       Create(args) => args.execute(conn),
       Edit(args) => args.execute(conn),
+      Clone(args) => args.execute(conn),
       Print(args) => args.execute(conn),
+      Relate(args) => args.execute(conn),
       Delete(args) => args.execute(conn),
+      History(args) => args.execute(conn),
+      Diff(args) => args.execute(conn),
+      Revert(args) => args.execute(conn),
       Dump(args) => args.execute(conn, database_file),
+      Doctor(args) => args.execute(conn),
+      Suggest(args) => args.execute(conn),
+      Steps(args) => args.execute(conn),
+      Cook(args) => args.execute(conn),
+      Schedule(args) => args.execute(conn),
+      Nutrition(args) => args.execute(conn),
+      Cost(args) => args.execute(conn),
+      Ingredient { subcommand } => subcommand.execute(conn),
+      Food { subcommand } => subcommand.execute(conn),
       Tag { subcommand } => subcommand.execute(conn),
+      Template { subcommand } => subcommand.execute(conn),
+      Drafts { subcommand } => subcommand.execute(conn),
+      Trash { subcommand } => subcommand.execute(conn),
     }
   }
 }
@@ -0,0 +1,67 @@
+use clap::Args;
+use diesel::SqliteConnection;
+use itertools::Itertools;
+use termimad::MadSkin;
+
+use crate::ui::pick_recipe;
+
+#[derive(Args)]
+pub struct NutritionArgs {
+  /// ID, name, or slug of the recipe
+  recipe: String,
+
+  /// Number of servings the recipe makes, to also show the nutrition per serving
+  #[arg(short, long)]
+  servings: Option<u32>,
+}
+
+impl NutritionArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let recipe = match pick_recipe(Some(&self.recipe), conn)? {
+      None => return Ok(()),
+      Some(recipe) => recipe,
+    };
+
+    let report = recipe.get_nutrition(conn)?;
+    let servings = self.servings.filter(|servings| *servings > 0);
+
+    let markdown = match servings {
+      None => {
+        let table_header = "| Nutrient | Total |\n|:- | -:|";
+        let table_body = report
+          .total
+          .rows()
+          .into_iter()
+          .map(|(label, total)| format!("|{}|{}|", label, total))
+          .join("\n");
+        format!("{}\n{}", table_header, table_body)
+      },
+      Some(servings) => {
+        let per_serving = report.total * (1.0 / servings as f64);
+        let table_header = format!("| Nutrient | Total | Per Serving ({}) |\n|:- | -:| -:|", servings);
+        let table_body = report
+          .total
+          .rows()
+          .into_iter()
+          .zip(per_serving.rows())
+          .map(|((label, total), (_, serving))| format!("|{}|{}|{}|", label, total, serving))
+          .join("\n");
+        format!("{}\n{}", table_header, table_body)
+      },
+    };
+
+    let skin = MadSkin::default();
+    skin.print_text(&format!("# Nutrition: {}\n{}", recipe.name, markdown));
+
+    if !report.gaps.is_empty() {
+      let gaps = report
+        .gaps
+        .iter()
+        .map(|gap| format!("* **{}**: {}", gap.ingredient, gap.reason))
+        .join("\n");
+      skin.print_text(&format!("Not counted in the totals:\n{}", gaps));
+    }
+
+    Ok(())
+  }
+}
@@ -1,60 +1,45 @@
 use clap::Args;
 use diesel::SqliteConnection;
-use horrorshow::{helper::doctype, html, Raw};
-use markdown::Options;
-use std::io::Write;
-use tempfile::Builder;
 use termimad::MadSkin;
 
-use crate::models::Recipe;
+use crate::links::{LinkIndex, LinkStyle};
+use crate::models::{Diet, SubstitutionPlan};
+use crate::ui::{open_print_page, pick_recipe};
 
 #[derive(Args)]
 pub struct PrintArgs {
-  /// Integer ID of the recipe to delete
-  id: i32,
+  /// ID, name, or slug of the recipe to print
+  ///
+  /// Opens a searchable list of recipes if not given
+  recipe: Option<String>,
 
   #[clap(short, long)]
   web: bool,
+
+  /// Swap in substitutions for ingredients that don't fit the diet
+  #[clap(long)]
+  substitute: Option<Diet>,
 }
 
 impl PrintArgs {
   pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
-    let recipe = match Recipe::find_optional(&self.id, conn)? {
-      None => {
-        println!("No such recipe: {}", self.id);
-        return Ok(());
-      },
+    let recipe = match pick_recipe(self.recipe.as_deref(), conn)? {
+      None => return Ok(()),
       Some(recipe) => recipe,
     };
 
+    let substitutions = match self.substitute {
+      Some(diet) => recipe.get_substitutions(diet, conn)?,
+      None => SubstitutionPlan::default(),
+    };
+
     if !self.web {
       let skin = MadSkin::default();
-      skin.print_text(&recipe.markdown_string(conn)?);
+      let links = LinkIndex::load(LinkStyle::Terminal, conn)?;
+      skin.print_text(&recipe.markdown_string_with_substitutions(&substitutions, &links, conn)?);
       return Ok(());
     }
 
-    let raw_body = markdown::to_html_with_options(&recipe.markdown_string(conn)?, &Options::gfm())?;
-    let (mut file, path) = Builder::new().suffix(".html").tempfile()?.keep()?;
-    write!(
-      file,
-      "{}",
-      html! {
-        : doctype::HTML;
-        html {
-          head {
-              title : &recipe.name;
-              style: Raw(include_str!("styles.css"));
-              script: "window.print();";
-          }
-          body(class = "markdown") {
-            : Raw(&raw_body)
-          }
-        }
-      },
-    )?;
-
-    open::that(path)?;
-
-    Ok(())
+    open_print_page(&recipe, &substitutions, conn)
   }
 }
@@ -0,0 +1,90 @@
+use clap::Args;
+use diesel::SqliteConnection;
+
+use crate::models::{RecipeRelation, RelationKind};
+use crate::ui::pick_recipe;
+
+#[derive(Args)]
+pub struct RelateArgs {
+  /// ID, name, or slug of the recipe
+  recipe: String,
+
+  /// ID, name, or slug of the recipe it's related to
+  related_recipe: String,
+
+  /// How the first recipe relates to the second, like "side-for" if it's a side dish
+  #[arg(short, long)]
+  kind: RelationKind,
+
+  /// Remove the relation instead of adding it
+  #[arg(short, long)]
+  remove: bool,
+}
+
+impl RelateArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let recipe = match pick_recipe(Some(&self.recipe), conn)? {
+      None => return Ok(()),
+      Some(recipe) => recipe,
+    };
+    let related_recipe = match pick_recipe(Some(&self.related_recipe), conn)? {
+      None => return Ok(()),
+      Some(recipe) => recipe,
+    };
+
+    if recipe.id == related_recipe.id {
+      println!("A recipe can't be related to itself");
+      return Ok(());
+    }
+
+    let relation = RecipeRelation {
+      recipe_id: recipe.id,
+      related_recipe_id: related_recipe.id,
+      kind: self.kind.id(),
+    };
+
+    // "Pairs with" goes both ways, so it might have been added from the other recipe
+    let reversed = RecipeRelation {
+      recipe_id: related_recipe.id,
+      related_recipe_id: recipe.id,
+      kind: self.kind.id(),
+    };
+    let symmetric = self.kind == RelationKind::PairsWith;
+    let description = format!("{}: {} {}", recipe.name, self.kind.label(false), related_recipe.name);
+
+    if self.remove {
+      let mut deleted = relation.delete(conn)?;
+      if symmetric {
+        deleted += reversed.delete(conn)?;
+      }
+
+      if deleted == 0 {
+        println!("No such relation: {}", description);
+      } else {
+        println!("Removed relation: {}", description);
+      }
+      return Ok(());
+    }
+
+    if relation.exists(conn)? || (symmetric && reversed.exists(conn)?) {
+      println!("Already related: {}", description);
+      return Ok(());
+    }
+
+    // "A is a side for B" and "B is a side for A" can't both be true
+    if !symmetric && reversed.exists(conn)? {
+      println!(
+        "Contradicts existing relation: {}: {} {}",
+        related_recipe.name,
+        self.kind.label(false),
+        recipe.name
+      );
+      return Ok(());
+    }
+
+    relation.insert(conn)?;
+    println!("Added relation: {}", description);
+
+    Ok(())
+  }
+}
@@ -0,0 +1,60 @@
+use clap::Args;
+use diesel::{Connection, QueryResult, SqliteConnection};
+use inquire::Confirm;
+
+use crate::models::RecipeRevision;
+use crate::ui::pick_recipe;
+
+#[derive(Args)]
+pub struct RevertArgs {
+  /// ID, name, or slug of the recipe
+  recipe: String,
+
+  /// Revision number to restore
+  revision: i32,
+
+  /// Skip the yes/no prompt
+  #[clap(long)]
+  force: bool,
+}
+
+impl RevertArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let recipe = match pick_recipe(Some(&self.recipe), conn)? {
+      None => return Ok(()),
+      Some(recipe) => recipe,
+    };
+
+    let revision = match RecipeRevision::find_by_revision(recipe.id, self.revision, conn)? {
+      None => {
+        println!("No such revision for recipe \"{}\": {}", recipe.name, self.revision);
+        return Ok(());
+      },
+      Some(revision) => revision,
+    };
+
+    if !self.force
+      && !Confirm::new(&format!(
+        "Really revert recipe \"{}\" to revision {} (Y/N):",
+        recipe.name, revision.revision
+      ))
+      .prompt()?
+    {
+      return Ok(());
+    }
+
+    // The current state is saved first, so the revert can itself be undone
+    let backup = conn.transaction(|conn| {
+      let backup = RecipeRevision::create_from_recipe(&recipe, conn)?;
+      revision.restore(&recipe, conn)?;
+      QueryResult::Ok(backup)
+    })?;
+
+    println!(
+      "Reverted recipe \"{}\" to revision {} (previous version saved as revision {})",
+      recipe.name, revision.revision, backup.revision
+    );
+
+    Ok(())
+  }
+}
@@ -0,0 +1,54 @@
+use clap::Args;
+use diesel::SqliteConnection;
+use std::collections::HashSet;
+use termimad::MadSkin;
+
+use crate::links::{LinkIndex, LinkStyle};
+use crate::schedule::{parse_clock_time, Schedule};
+use crate::ui::{open_markdown_page, pick_recipe};
+
+#[derive(Args)]
+pub struct ScheduleArgs {
+  /// IDs, names, or slugs of the recipes to make
+  #[arg(required = true)]
+  recipes: Vec<String>,
+
+  /// When everything should be ready, like 18:30
+  #[arg(short, long, value_parser = parse_clock_time)]
+  serve_at: i32,
+
+  /// Open the schedule in the browser for printing
+  #[arg(short, long)]
+  web: bool,
+}
+
+impl ScheduleArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let mut recipes = Vec::new();
+    for reference in self.recipes.iter() {
+      let recipe = match pick_recipe(Some(reference), conn)? {
+        None => return Ok(()),
+        Some(recipe) => recipe,
+      };
+
+      let steps = recipe.get_or_update_steps(conn)?;
+      if steps.is_empty() {
+        println!("No numbered steps in the instructions of recipe: {}", recipe.name);
+        return Ok(());
+      }
+      recipes.push((recipe, steps));
+    }
+
+    let schedule = Schedule::new(self.serve_at, recipes);
+    if self.web {
+      // The printed schedule is a single page, so linked recipes are written as plain names
+      let links = LinkIndex::load(LinkStyle::Html, conn)?.with_linkable_ids(HashSet::new());
+      return open_markdown_page("Schedule", &schedule.markdown_string(&links));
+    }
+
+    let links = LinkIndex::load(LinkStyle::Terminal, conn)?;
+    let skin = MadSkin::default();
+    skin.print_text(&schedule.markdown_string(&links));
+    Ok(())
+  }
+}
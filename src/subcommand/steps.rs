@@ -0,0 +1,83 @@
+use clap::Args;
+use diesel::SqliteConnection;
+use itertools::Itertools;
+use termimad::MadSkin;
+
+use crate::links::{LinkIndex, LinkStyle};
+use crate::models::Recipe;
+use crate::steps::format_duration;
+use crate::ui::pick_recipe;
+
+#[derive(Args)]
+pub struct StepsArgs {
+  /// ID, name, or slug of the recipe
+  ///
+  /// Required unless updating every recipe
+  #[arg(required_unless_present = "update_all")]
+  recipe: Option<String>,
+
+  /// Re-read the steps from the instructions before showing them
+  #[arg(short, long)]
+  update: bool,
+
+  /// Re-read the steps of every recipe, like after upgrading from a version without steps
+  #[arg(long, conflicts_with = "recipe")]
+  update_all: bool,
+}
+
+impl StepsArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    if self.update_all {
+      let recipes = Recipe::all(conn)?;
+      for recipe in recipes.iter() {
+        recipe.update_steps(conn)?;
+      }
+      println!("Updated the steps of {} recipes", recipes.len());
+      return Ok(());
+    }
+
+    let recipe = match pick_recipe(self.recipe.as_deref(), conn)? {
+      None => return Ok(()),
+      Some(recipe) => recipe,
+    };
+
+    let steps = if self.update {
+      recipe.update_steps(conn)?
+    } else {
+      recipe.get_or_update_steps(conn)?
+    };
+
+    if steps.is_empty() {
+      println!("No numbered steps in the instructions of recipe: {}", recipe.name);
+      return Ok(());
+    }
+
+    let links = LinkIndex::load(LinkStyle::Terminal, conn)?;
+    let table_header = "| Step | Instruction | Time | Temperature | Ingredients |\n| -:|:- |:- |:- |:- |";
+    let table_body = steps
+      .into_iter()
+      .map(|step| -> super::Result<_> {
+        let ingredients = step
+          .get_ingredients_ordered(conn)?
+          .into_iter()
+          .map(|i| i.name)
+          .join(", ");
+        Ok(format!(
+          "|{}|{}|{}|{}|{}|",
+          step.step_number,
+          links.resolve_links(&step.text_markdown).replace('\n', " "),
+          step.duration_seconds.map(format_duration).unwrap_or_default(),
+          step.temperature.unwrap_or_default(),
+          ingredients
+        ))
+      })
+      .collect::<super::Result<Vec<_>>>()?
+      .join("\n");
+
+    let markdown = format!("# Steps: {}\n{}\n{}", recipe.name, table_header, table_body);
+    let skin = MadSkin::default();
+    skin.print_text(&markdown);
+
+    Ok(())
+  }
+}
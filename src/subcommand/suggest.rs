@@ -0,0 +1,73 @@
+use clap::Args;
+use diesel::SqliteConnection;
+use itertools::Itertools;
+use rand::seq::SliceRandom;
+use std::collections::HashSet;
+use termimad::MadSkin;
+
+use crate::models::{Recipe, Tag};
+
+#[derive(Args)]
+pub struct SuggestArgs {
+  /// Only suggest recipes that have this tag (can be repeated)
+  #[clap(short, long = "tag")]
+  tags: Vec<String>,
+
+  /// Number of recipes to suggest
+  #[clap(short = 'n', long, default_value_t = 1)]
+  count: usize,
+}
+
+impl SuggestArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    // Recipes must have ALL of the requested tags
+    let mut allowed_recipes: Option<HashSet<i32>> = None;
+    for tag_id in self.tags.iter() {
+      let tag = match Tag::find_optional(tag_id, conn)? {
+        Some(tag) => tag,
+        None => {
+          println!("No such tag: {}", tag_id);
+          return Ok(());
+        },
+      };
+
+      let tagged_recipes: HashSet<_> = tag.get_recipe_tags(conn)?.into_iter().map(|rt| rt.recipe_id).collect();
+      allowed_recipes = Some(match allowed_recipes {
+        None => tagged_recipes,
+        Some(recipes) => recipes.intersection(&tagged_recipes).copied().collect(),
+      });
+    }
+
+    let candidates: Vec<Recipe> = Recipe::all_ordered(conn)?
+      .into_iter()
+      .filter(|r| allowed_recipes.as_ref().is_none_or(|allowed| allowed.contains(&r.id)))
+      .collect();
+
+    if candidates.is_empty() {
+      println!("No recipes match the given filters");
+      return Ok(());
+    }
+
+    // No cooking history is recorded, so every candidate is equally likely
+    let suggestions = candidates.choose_multiple(&mut rand::thread_rng(), self.count);
+
+    let table_header = "| ID | Recipe Name | Tags |\n| -:|:- |:- |";
+    let table_body = suggestions
+      .map(|recipe| -> super::Result<_> {
+        let tags = recipe.get_tags_ordered(conn)?;
+        Ok(format!(
+          "|{}|{}|{}|",
+          recipe.id,
+          recipe.name,
+          tags.into_iter().map(|t| t.name).join(", ")
+        ))
+      })
+      .collect::<super::Result<Vec<_>>>()?
+      .join("\n");
+
+    let skin = MadSkin::default();
+    skin.print_text(&format!("{}\n{}", table_header, table_body));
+
+    Ok(())
+  }
+}
@@ -3,22 +3,22 @@ use diesel::{Connection, SqliteConnection};
 use inquire::MultiSelect;
 use std::collections::HashSet;
 
-use crate::models::{Recipe, Tag};
+use crate::models::Recipe;
+use crate::ui::{fuzzy_filter, pick_tag};
 
 #[derive(Args)]
 pub struct ApplyArgs {
   /// Unique ID associated with the tag
-  id: String,
+  ///
+  /// Opens a searchable list of tags if not given
+  id: Option<String>,
 }
 
 impl ApplyArgs {
   pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
-    let tag = match Tag::find_optional(&self.id, conn)? {
+    let tag = match pick_tag(self.id.as_deref(), conn)? {
       Some(tag) => tag,
-      None => {
-        println!("No such tag: {}", self.id);
-        return Ok(());
-      },
+      None => return Ok(()),
     };
 
     let all_recipes = Recipe::all_ordered(conn)?;
@@ -35,6 +35,7 @@ impl ApplyArgs {
       all_recipes.iter().map(|r| &r.name).collect(),
     )
     .with_default(&selected_recipes)
+    .with_filter(&fuzzy_filter)
     .raw_prompt_skippable()?;
 
     let new_selected_recipes: Vec<_> = match new_selected_recipes {
@@ -45,10 +46,21 @@ impl ApplyArgs {
       Some(recipes) => recipes.into_iter().map(|v| v.index).collect(),
     };
 
+    // Recipes in the trash aren't shown, so keep their tags as-is
+    let trashed_recipes: Vec<_> = tagged_recipes
+      .iter()
+      .filter(|id| !all_recipes.iter().any(|r| r.id == **id))
+      .collect();
+
     // Transaction to update the selected recipes
     conn.transaction(|conn| {
-      //
-      tag.set_recipes_ids(new_selected_recipes.iter().map(|index| &all_recipes[*index].id), conn)
+      tag.set_recipes_ids(
+        new_selected_recipes
+          .iter()
+          .map(|index| &all_recipes[*index].id)
+          .chain(trashed_recipes),
+        conn,
+      )
     })?;
 
     if new_selected_recipes.len() != 1 {
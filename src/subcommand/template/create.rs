@@ -0,0 +1,68 @@
+use clap::Args;
+use diesel::SqliteConnection;
+use std::path::PathBuf;
+
+use super::{read_document, save_template};
+use crate::models::RecipeTemplate;
+use crate::ui::{pick_recipe, IngredientLine, RecipeDocument};
+
+#[derive(Args)]
+pub struct CreateArgs {
+  /// Unique ID associated with the template, used with `recipe create --template`
+  id: String,
+
+  /// Pretty-print name for the template
+  #[arg(short, long)]
+  name: Option<String>,
+
+  /// Start from the ingredients, instructions, notes, and tags of an existing recipe
+  #[arg(long, value_name = "RECIPE")]
+  from_recipe: Option<String>,
+
+  /// Read the template from a recipe document instead of opening the editor ("-" reads from stdin)
+  #[arg(long, value_name = "FILE", conflicts_with = "from_recipe")]
+  from_file: Option<PathBuf>,
+}
+
+impl CreateArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    if RecipeTemplate::exists_from_id(&self.id, conn)? {
+      println!("Error, existing template: {}", self.id);
+      return Ok(());
+    }
+
+    let mut document = RecipeDocument {
+      name: self.name.clone().unwrap_or_else(|| self.id.clone()),
+      tags: Vec::new(),
+      ingredients: Vec::new(),
+      instructions_markdown: String::new(),
+      notes_markdown: String::new(),
+    };
+
+    if let Some(reference) = self.from_recipe.as_deref() {
+      let recipe = match pick_recipe(Some(reference), conn)? {
+        None => return Ok(()),
+        Some(recipe) => recipe,
+      };
+
+      document.tags = recipe.get_tags(conn)?.into_iter().map(|t| t.id).collect();
+      document.ingredients = recipe
+        .get_ingredients_with_metadata(conn)?
+        .into_iter()
+        .map(|(ingredient, ri)| IngredientLine {
+          quantity: ri.quantity,
+          name: ingredient.name,
+          notes_markdown: ri.notes_markdown,
+        })
+        .collect();
+      document.instructions_markdown = recipe.instructions_markdown;
+      document.notes_markdown = recipe.notes_markdown;
+    }
+
+    let document = read_document(document, self.from_file.as_deref(), conn)?;
+    save_template(&self.id, document, conn)?;
+    println!("Created template: {}", self.id);
+
+    Ok(())
+  }
+}
@@ -0,0 +1,22 @@
+use clap::Args;
+use diesel::SqliteConnection;
+
+use crate::models::RecipeTemplate;
+
+#[derive(Args)]
+pub struct DeleteArgs {
+  /// ID for the template
+  id: String,
+}
+
+impl DeleteArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    if !RecipeTemplate::exists_from_id(&self.id, conn)? {
+      println!("No such template: {}", self.id);
+      return Ok(());
+    }
+
+    RecipeTemplate::delete_from_id(&self.id, conn)?;
+    Ok(())
+  }
+}
@@ -0,0 +1,35 @@
+use clap::Args;
+use diesel::SqliteConnection;
+use std::path::PathBuf;
+
+use super::{read_document, save_template, template_document};
+use crate::models::RecipeTemplate;
+
+#[derive(Args)]
+pub struct EditArgs {
+  /// Unique ID associated with the template
+  id: String,
+
+  /// Replace the template with a recipe document instead of opening the editor ("-" reads from stdin)
+  #[arg(long, value_name = "FILE")]
+  from_file: Option<PathBuf>,
+}
+
+impl EditArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let template = match RecipeTemplate::find_optional(&self.id, conn)? {
+      Some(template) => template,
+      None => {
+        println!("No such template: {}", self.id);
+        return Ok(());
+      },
+    };
+
+    let document = template_document(&template, conn)?;
+    let document = read_document(document, self.from_file.as_deref(), conn)?;
+    save_template(&self.id, document, conn)?;
+    println!("Changes saved!");
+
+    Ok(())
+  }
+}
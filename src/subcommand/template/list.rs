@@ -0,0 +1,34 @@
+use clap::Args;
+use diesel::SqliteConnection;
+use itertools::Itertools;
+use termimad::MadSkin;
+
+use crate::models::RecipeTemplate;
+use crate::ui::IngredientLine;
+
+#[derive(Args)]
+pub struct ListArgs;
+
+impl ListArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let table_header = "| Template ID | Name | Tags | Ingredients |\n| -:|:- |:- |:- |";
+    let table_body = RecipeTemplate::all_ordered(conn)?
+      .into_iter()
+      .map(|template| -> super::Result<_> {
+        let tags = template.get_tags_ordered(conn)?.into_iter().map(|t| t.name).join(", ");
+        let ingredients = IngredientLine::parse_all(&template.ingredients_text)
+          .into_iter()
+          .map(|line| line.name)
+          .join(", ");
+        Ok(format!("|{}|{}|{}|{}|", template.id, template.name, tags, ingredients))
+      })
+      .collect::<super::Result<Vec<_>>>()?
+      .join("\n");
+
+    let markdown = format!("{}\n{}", table_header, table_body);
+    let skin = MadSkin::default();
+    skin.print_text(&markdown);
+
+    Ok(())
+  }
+}
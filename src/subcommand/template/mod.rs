@@ -0,0 +1,108 @@
+mod create;
+mod delete;
+mod edit;
+mod list;
+
+use clap::Subcommand;
+use crossterm::style::Stylize;
+use diesel::{Connection, SqliteConnection};
+use inquire::Editor;
+use std::path::Path;
+
+use crate::models::{RecipeTemplate, Tag};
+use crate::ui::{IngredientLine, RecipeDocument};
+
+pub use super::Result;
+
+#[derive(Subcommand)]
+pub enum TemplateSubcommand {
+  /// List all recipe templates
+  List(list::ListArgs),
+
+  /// Create a template for new recipes
+  Create(create::CreateArgs),
+
+  /// Edit a template
+  Edit(edit::EditArgs),
+
+  /// Delete a template
+  Delete(delete::DeleteArgs),
+}
+
+impl TemplateSubcommand {
+  pub fn execute(self, conn: &mut SqliteConnection) -> Result<()> {
+    use TemplateSubcommand::*;
+    match self {
+      List(args) => args.execute(conn),
+      Create(args) => args.execute(conn),
+      Edit(args) => args.execute(conn),
+      Delete(args) => args.execute(conn),
+    }
+  }
+}
+
+/// Template written in the same format as a recipe document, where the name is the template name
+fn template_document(template: &RecipeTemplate, conn: &mut SqliteConnection) -> Result<RecipeDocument> {
+  Ok(RecipeDocument {
+    name: template.name.clone(),
+    tags: template.get_tags(conn)?.into_iter().map(|t| t.id).collect(),
+    ingredients: IngredientLine::parse_all(&template.ingredients_text),
+    instructions_markdown: template.instructions_markdown.clone(),
+    notes_markdown: template.notes_markdown.clone(),
+  })
+}
+
+/// Read the template from a file, or from the editor if no file is given
+fn read_document(
+  document: RecipeDocument,
+  from_file: Option<&Path>,
+  conn: &mut SqliteConnection,
+) -> Result<RecipeDocument> {
+  let all_tags = Tag::all_ordered(conn)?;
+  if let Some(path) = from_file {
+    let document = RecipeDocument::parse_any(&RecipeDocument::read_from(path)?)?;
+    check_tags(&document, &all_tags)?;
+    return Ok(document);
+  }
+
+  let mut text = document.to_markdown(&all_tags);
+  loop {
+    text = Editor::new("Template")
+      .with_file_extension(".md")
+      .with_predefined_text(&text)
+      .prompt()?;
+
+    match RecipeDocument::parse(&text).and_then(|document| check_tags(&document, &all_tags).map(|_| document)) {
+      Ok(document) => return Ok(document),
+      Err(e) => {
+        println!("{}: {}", "Error".red(), e);
+        text = RecipeDocument::with_error(&text, &e);
+      },
+    }
+  }
+}
+
+fn check_tags(document: &RecipeDocument, all_tags: &[Tag]) -> std::result::Result<(), String> {
+  match document.tags.iter().find(|id| !all_tags.iter().any(|t| &&t.id == id)) {
+    Some(id) => Err(format!("No such tag: {}", id)),
+    None => Ok(()),
+  }
+}
+
+/// Save the template along with its tags
+fn save_template(id: &str, document: RecipeDocument, conn: &mut SqliteConnection) -> Result<()> {
+  let template = RecipeTemplate {
+    id: id.to_string(),
+    name: document.name,
+    ingredients_text: IngredientLine::format_all(&document.ingredients),
+    instructions_markdown: document.instructions_markdown,
+    notes_markdown: document.notes_markdown,
+  };
+
+  conn.transaction(|conn| {
+    template.insert_or_update(conn)?;
+    template.set_tags_ids(document.tags.iter(), conn)
+  })?;
+
+  Ok(())
+}
@@ -0,0 +1,35 @@
+use clap::Args;
+use diesel::SqliteConnection;
+use inquire::Confirm;
+
+use crate::models::Recipe;
+
+#[derive(Args)]
+pub struct EmptyArgs {
+  /// Skip the yes/no prompt
+  #[clap(long)]
+  force: bool,
+}
+
+impl EmptyArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let trash_count = Recipe::all_deleted(conn)?.len();
+    if trash_count == 0 {
+      println!("The trash is already empty");
+      return Ok(());
+    }
+
+    if self.force
+      || Confirm::new(&format!(
+        "Permanently delete {} recipe(s) in the trash (Y/N):",
+        trash_count
+      ))
+      .prompt()?
+    {
+      let deleted_count = Recipe::empty_trash(conn)?;
+      println!("Permanently deleted {} recipe(s)", deleted_count);
+    }
+
+    Ok(())
+  }
+}
@@ -0,0 +1,32 @@
+use clap::Args;
+use diesel::SqliteConnection;
+use itertools::Itertools;
+use termimad::MadSkin;
+
+use crate::models::Recipe;
+
+#[derive(Args)]
+pub struct ListArgs;
+
+impl ListArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let table_header = "| ID | Recipe Name | Deleted At |\n| -:|:- |:- |";
+    let table_body = Recipe::all_deleted(conn)?
+      .into_iter()
+      .map(|recipe| {
+        format!(
+          "|{}|{}|{}|",
+          recipe.id,
+          recipe.name,
+          recipe.deleted_at.unwrap_or_default()
+        )
+      })
+      .join("\n");
+
+    let markdown = format!("{}\n{}", table_header, table_body);
+    let skin = MadSkin::default();
+    skin.print_text(&markdown);
+
+    Ok(())
+  }
+}
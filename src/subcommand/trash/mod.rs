@@ -0,0 +1,31 @@
+mod empty;
+mod list;
+mod restore;
+
+use clap::Subcommand;
+use diesel::SqliteConnection;
+
+pub use super::Result;
+
+#[derive(Subcommand)]
+pub enum TrashSubcommand {
+  /// List all recipes in the trash
+  List(list::ListArgs),
+
+  /// Take a recipe back out of the trash
+  Restore(restore::RestoreArgs),
+
+  /// Permanently delete all recipes in the trash
+  Empty(empty::EmptyArgs),
+}
+
+impl TrashSubcommand {
+  pub fn execute(self, conn: &mut SqliteConnection) -> Result<()> {
+    use TrashSubcommand::*;
+    match self {
+      List(args) => args.execute(conn),
+      Restore(args) => args.execute(conn),
+      Empty(args) => args.execute(conn),
+    }
+  }
+}
@@ -0,0 +1,27 @@
+use clap::Args;
+use diesel::SqliteConnection;
+
+use crate::models::Recipe;
+
+#[derive(Args)]
+pub struct RestoreArgs {
+  /// Integer ID of the recipe to restore
+  id: i32,
+}
+
+impl RestoreArgs {
+  pub fn execute(self, conn: &mut SqliteConnection) -> super::Result<()> {
+    let recipe = match Recipe::find_optional(&self.id, conn)? {
+      Some(recipe) if recipe.is_deleted() => recipe,
+      _ => {
+        println!("No such recipe in the trash: {}", self.id);
+        return Ok(());
+      },
+    };
+
+    recipe.restore(conn)?;
+    println!("Restored recipe: {} (ID: {})", recipe.name, recipe.id);
+
+    Ok(())
+  }
+}
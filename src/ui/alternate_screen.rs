@@ -0,0 +1,38 @@
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{cursor, execute};
+use std::io::{self, stdout};
+
+/// Full-screen raw mode terminal, restored when dropped
+pub struct AlternateScreen;
+
+impl AlternateScreen {
+  pub fn enter() -> io::Result<Self> {
+    let screen = Self;
+    screen.resume()?;
+    Ok(screen)
+  }
+
+  /// Temporarily go back to the normal terminal, e.g. to show an inquire prompt
+  pub fn suspend(&self) -> io::Result<()> {
+    execute!(stdout(), cursor::Show, LeaveAlternateScreen)?;
+    disable_raw_mode()
+  }
+
+  pub fn resume(&self) -> io::Result<()> {
+    enable_raw_mode()?;
+    execute!(stdout(), EnterAlternateScreen, cursor::Hide)
+  }
+}
+
+impl Drop for AlternateScreen {
+  fn drop(&mut self) {
+    let _ = self.suspend();
+  }
+}
+
+/// Truncate or pad the string to exactly fill the given width
+pub fn fit_width(input: &str, width: u16) -> String {
+  let width = width as usize;
+  let truncated: String = input.chars().take(width).collect();
+  format!("{:width$}", truncated, width = width)
+}
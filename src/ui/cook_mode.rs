@@ -0,0 +1,316 @@
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::style::{Print, Stylize};
+use crossterm::terminal::{self, Clear, ClearType};
+use crossterm::{cursor, queue};
+use diesel::SqliteConnection;
+use itertools::Itertools;
+use std::io::{stdout, Write};
+use std::time::{Duration, Instant};
+use termimad::{Alignment, Area, MadSkin, MadView};
+
+use super::{fit_width, AlternateScreen};
+use crate::links::{LinkIndex, LinkStyle};
+use crate::models::Recipe;
+use crate::steps::{find_durations, format_duration};
+use crate::subcommand;
+
+const HELP_TEXT: &str = "←→ step  1-9 start/stop timer  x dismiss finished timers  PgUp/PgDn scroll  q quit";
+
+/// Finished timers ring again this often until they are dismissed
+const RING_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Full-screen view of one instruction step at a time, with countdown timers
+pub struct CookMode {
+  recipe_name: String,
+  steps: Vec<CookStep>,
+  current: usize,
+  timers: Vec<Timer>,
+  view: Option<MadView>,
+}
+
+struct CookStep {
+  text_markdown: String,
+  ingredients: Vec<String>,
+  durations: Vec<(String, Duration)>,
+}
+
+/// Countdown started from a duration in a step
+struct Timer {
+  step_index: usize,
+  duration_index: usize,
+  label: String,
+  ends_at: Instant,
+  last_rang: Option<Instant>,
+}
+
+impl CookMode {
+  /// Returns None if the recipe doesn't have numbered steps
+  pub fn new(recipe: &Recipe, conn: &mut SqliteConnection) -> subcommand::Result<Option<Self>> {
+    let steps = recipe.get_or_update_steps(conn)?;
+    if steps.is_empty() {
+      return Ok(None);
+    }
+
+    let links = LinkIndex::load(LinkStyle::Terminal, conn)?;
+    let recipe_ingredients = recipe.get_ingredients_with_metadata(conn)?;
+    let steps = steps
+      .into_iter()
+      .map(|step| -> subcommand::Result<_> {
+        let ingredients = step
+          .get_ingredients(conn)?
+          .into_iter()
+          .map(|ingredient| {
+            let quantity = recipe_ingredients
+              .iter()
+              .find(|(i, _)| i.id == ingredient.id)
+              .map(|(_, ri)| ri.quantity.trim())
+              .unwrap_or_default();
+            format!("{} {}", quantity, ingredient.name).trim().to_string()
+          })
+          .collect();
+
+        let durations = find_durations(&step.text_markdown)
+          .into_iter()
+          .map(|mention| (mention.text, Duration::from_secs(mention.seconds.max(0) as u64)))
+          .collect();
+
+        Ok(CookStep {
+          text_markdown: links.resolve_links(&step.text_markdown),
+          ingredients,
+          durations,
+        })
+      })
+      .collect::<subcommand::Result<_>>()?;
+
+    Ok(Some(Self {
+      recipe_name: recipe.name.clone(),
+      steps,
+      current: 0,
+      timers: Vec::new(),
+      view: None,
+    }))
+  }
+
+  pub fn run(mut self) -> subcommand::Result<()> {
+    let _screen = AlternateScreen::enter()?;
+
+    loop {
+      self.ring_finished_timers()?;
+      self.render()?;
+
+      // Wake up every so often to update the timers
+      if !event::poll(Duration::from_millis(250))? {
+        continue;
+      }
+      let key = match event::read()? {
+        Event::Key(key) if key.kind == KeyEventKind::Press => key,
+        Event::Resize(_, _) => {
+          self.view = None;
+          continue;
+        },
+        _ => continue,
+      };
+
+      if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+        break;
+      }
+
+      match key.code {
+        KeyCode::Char('q') | KeyCode::Esc => break,
+        KeyCode::Right | KeyCode::Down | KeyCode::Char(' ') | KeyCode::Char('l') | KeyCode::Char('j') => {
+          self.go_to_step(self.current + 1)
+        },
+        KeyCode::Left | KeyCode::Up | KeyCode::Char('h') | KeyCode::Char('k') => {
+          self.go_to_step(self.current.saturating_sub(1))
+        },
+        KeyCode::Home => self.go_to_step(0),
+        KeyCode::End => self.go_to_step(self.steps.len() - 1),
+        KeyCode::PageUp => {
+          if let Some(view) = self.view.as_mut() {
+            view.try_scroll_pages(-1);
+          }
+        },
+        KeyCode::PageDown => {
+          if let Some(view) = self.view.as_mut() {
+            view.try_scroll_pages(1);
+          }
+        },
+        KeyCode::Char(c) if c.is_ascii_digit() && c != '0' => self.toggle_timer(c as usize - '1' as usize),
+        KeyCode::Char('x') => self.dismiss_finished_timers(),
+        _ => {},
+      }
+    }
+
+    Ok(())
+  }
+
+  fn go_to_step(&mut self, index: usize) {
+    let index = index.min(self.steps.len() - 1);
+    if index != self.current {
+      self.current = index;
+      self.view = None;
+    }
+  }
+
+  /// Start a timer for a duration in the current step, or stop it if it's already running
+  fn toggle_timer(&mut self, duration_index: usize) {
+    let Some((text, duration)) = self.steps[self.current].durations.get(duration_index) else {
+      return;
+    };
+
+    let existing = self
+      .timers
+      .iter()
+      .position(|t| t.step_index == self.current && t.duration_index == duration_index);
+    match existing {
+      Some(position) => {
+        self.timers.remove(position);
+      },
+      None => self.timers.push(Timer {
+        step_index: self.current,
+        duration_index,
+        label: format!("Step {}: {}", self.current + 1, text),
+        ends_at: Instant::now() + *duration,
+        last_rang: None,
+      }),
+    }
+  }
+
+  /// Stop every timer that is done, including ones started on other steps
+  fn dismiss_finished_timers(&mut self) {
+    let now = Instant::now();
+    self.timers.retain(|timer| timer.ends_at > now);
+  }
+
+  fn ring_finished_timers(&mut self) -> subcommand::Result<()> {
+    let now = Instant::now();
+    let mut ring = false;
+    for timer in self.timers.iter_mut().filter(|t| t.ends_at <= now) {
+      if timer.last_rang.is_none_or(|last| now - last >= RING_INTERVAL) {
+        timer.last_rang = Some(now);
+        ring = true;
+      }
+    }
+
+    if ring {
+      let mut out = stdout();
+      queue!(out, Print('\x07'))?;
+      out.flush()?;
+    }
+    Ok(())
+  }
+
+  fn render(&mut self) -> subcommand::Result<()> {
+    let (width, height) = terminal::size()?;
+    let step = &self.steps[self.current];
+    let mut out = stdout();
+    queue!(out, Clear(ClearType::All))?;
+
+    // Header with the recipe name and position
+    let header = format!(
+      "{} - Step {} of {}",
+      self.recipe_name,
+      self.current + 1,
+      self.steps.len()
+    );
+    queue!(
+      out,
+      cursor::MoveTo(0, 0),
+      Print(fit_width(&header, width).bold().reverse())
+    )?;
+
+    // Ingredients used in this step
+    let mut row = 2;
+    if !step.ingredients.is_empty() {
+      queue!(out, cursor::MoveTo(0, row), Print("You need:".bold()))?;
+      for ingredient in step.ingredients.iter() {
+        row += 1;
+        queue!(
+          out,
+          cursor::MoveTo(2, row),
+          Print(fit_width(&format!("• {}", ingredient), width.saturating_sub(2)))
+        )?;
+      }
+      row += 2;
+    }
+
+    // Timers at the bottom, above the help text
+    let now = Instant::now();
+    let step_timers = step.durations.iter().enumerate().map(|(index, (text, duration))| {
+      let running = self
+        .timers
+        .iter()
+        .any(|t| t.step_index == self.current && t.duration_index == index);
+      let action = if running { "stop" } else { "start" };
+      format!(
+        "[{}] {} {} ({})",
+        index + 1,
+        action,
+        text,
+        format_duration(duration.as_secs() as i32)
+      )
+    });
+    let running_timers = self.timers.iter().map(|timer| {
+      if timer.ends_at <= now {
+        format!("⏰ {} - DONE", timer.label)
+      } else {
+        let remaining = timer.ends_at - now;
+        format!("⏲ {} - {}", timer.label, format_countdown(remaining))
+      }
+    });
+    let timer_lines = step_timers.chain(running_timers).collect_vec();
+
+    let footer_row = height.saturating_sub(1);
+    let timers_top = footer_row.saturating_sub(timer_lines.len() as u16 + 1);
+    for (index, line) in timer_lines.iter().enumerate() {
+      let line = fit_width(line, width);
+      queue!(out, cursor::MoveTo(0, timers_top + 1 + index as u16))?;
+      if line.contains("DONE") {
+        queue!(out, Print(line.bold().red()))?;
+      } else {
+        queue!(out, Print(line))?;
+      }
+    }
+    queue!(
+      out,
+      cursor::MoveTo(0, footer_row),
+      Print(fit_width(HELP_TEXT, width).dark_grey())
+    )?;
+
+    // The step itself, in bold and centered in the remaining space
+    let step_area = Area::new(2, row, width.saturating_sub(4), timers_top.saturating_sub(row));
+    if self.view.is_none() {
+      let mut skin = MadSkin::default();
+      skin.paragraph.align = Alignment::Center;
+      skin
+        .paragraph
+        .compound_style
+        .add_attr(termimad::crossterm::style::Attribute::Bold);
+      self.view = Some(MadView::from(step.text_markdown.clone(), step_area.clone(), skin));
+    }
+
+    // Termimad can't render into an empty area
+    if let Some(view) = self
+      .view
+      .as_mut()
+      .filter(|_| step_area.width > 1 && step_area.height > 0)
+    {
+      view.resize(&step_area);
+      view.write_on(&mut out)?;
+    }
+
+    out.flush()?;
+    Ok(())
+  }
+}
+
+/// Remaining time like "4:05" or "1:02:03"
+fn format_countdown(remaining: Duration) -> String {
+  let seconds = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+  let (hours, minutes, seconds) = (seconds / 3600, (seconds % 3600) / 60, seconds % 60);
+  if hours > 0 {
+    format!("{}:{:02}:{:02}", hours, minutes, seconds)
+  } else {
+    format!("{}:{:02}", minutes, seconds)
+  }
+}
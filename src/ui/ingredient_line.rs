@@ -0,0 +1,143 @@
+use itertools::Itertools;
+use serde::Deserialize;
+
+use crate::quantity::Quantity;
+
+/// Ingredient as written in the ingredients list, before it is matched against the database
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IngredientLine {
+  #[serde(default)]
+  pub quantity: String,
+  pub name: String,
+  #[serde(default, rename = "notes")]
+  pub notes_markdown: String,
+}
+
+impl IngredientLine {
+  /// Parse one ingredient per line, where an optional "- " line after an ingredient holds its notes
+  ///
+  /// Lines are either written as "quantity: name", or as "2 cups flour, sifted" where
+  ///   the quantity is detected automatically and anything after the first comma is a note.
+  pub fn parse_all(input: &str) -> Vec<Self> {
+    let mut result = Vec::new();
+
+    let mut lines = input.lines().map(|s| s.trim()).filter(|s| !s.is_empty()).peekable();
+    while let Some(line) = lines.next() {
+      // Only a real quantity before the colon means "quantity: name", so "salt (note: kosher)" stays whole
+      let (quantity, ingredient, inline_notes) = match line.split_once(':') {
+        Some((quantity, ingredient)) if quantity.trim().is_empty() || Quantity::parse(quantity).is_some() => {
+          (quantity.trim(), ingredient.trim(), "")
+        },
+        _ => Self::split_natural(line),
+      };
+
+      // Next line is notes if it starts with "- "
+      let notes = match lines.peek() {
+        Some(next_line) if next_line.starts_with("- ") => lines.next().unwrap().strip_prefix("- ").unwrap(),
+        _ => "",
+      };
+
+      result.push(Self {
+        quantity: quantity.to_string(),
+        name: ingredient.to_string(),
+        notes_markdown: [inline_notes, notes].into_iter().filter(|n| !n.is_empty()).join(", "),
+      });
+    }
+
+    result
+  }
+
+  /// Split "2 cups flour, sifted" into the quantity, name, and notes
+  fn split_natural(line: &str) -> (&str, &str, &str) {
+    let (quantity, rest) = match Quantity::split_prefix(line) {
+      Some((_, quantity, rest)) if !rest.is_empty() => (quantity, rest),
+      _ => ("", line),
+    };
+
+    match rest.split_once(',') {
+      Some((name, notes)) if !name.trim().is_empty() => (quantity, name.trim(), notes.trim()),
+      _ => (quantity, rest, ""),
+    }
+  }
+
+  /// Markdown table showing how each line was understood
+  pub fn preview_table<'a>(lines: impl IntoIterator<Item = &'a IngredientLine>) -> String {
+    let table_header = "| Quantity | Ingredient | Notes |\n|:- |:- |:- |";
+    let table_body = lines
+      .into_iter()
+      .map(|line| {
+        let quantity = Quantity::parse(&line.quantity)
+          .map(|q| q.to_string())
+          .unwrap_or_else(|| line.quantity.clone());
+        format!("|{}|{}|{}|", quantity, line.name, line.notes_markdown)
+      })
+      .join("\n");
+
+    format!("{}\n{}", table_header, table_body)
+  }
+
+  /// Inverse of `parse_all`
+  pub fn format_all<'a>(lines: impl IntoIterator<Item = &'a IngredientLine>) -> String {
+    lines.into_iter().map(IngredientLine::to_string).join("\n")
+  }
+}
+
+impl std::fmt::Display for IngredientLine {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}: {}", self.quantity, self.name)?;
+    if !self.notes_markdown.is_empty() {
+      write!(f, "\n- {}", self.notes_markdown)?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn line(quantity: &str, name: &str, notes_markdown: &str) -> IngredientLine {
+    IngredientLine {
+      quantity: quantity.to_string(),
+      name: name.to_string(),
+      notes_markdown: notes_markdown.to_string(),
+    }
+  }
+
+  #[test]
+  fn natural_lines() {
+    assert_eq!(
+      IngredientLine::parse_all("2 cups all-purpose flour, sifted\n- Or bread flour"),
+      vec![line("2 cups", "all-purpose flour", "sifted, Or bread flour")]
+    );
+    assert_eq!(IngredientLine::parse_all("Salt"), vec![line("", "Salt", "")]);
+  }
+
+  #[test]
+  fn colon_fallback() {
+    assert_eq!(
+      IngredientLine::parse_all("1 Cup: Flour, sifted\n- Note"),
+      vec![line("1 Cup", "Flour, sifted", "Note")]
+    );
+    assert_eq!(IngredientLine::parse_all(": Salt"), vec![line("", "Salt", "")]);
+  }
+
+  #[test]
+  fn colons_without_a_quantity() {
+    assert_eq!(
+      IngredientLine::parse_all("1 tsp salt (note: kosher)"),
+      vec![line("1 tsp", "salt (note: kosher)", "")]
+    );
+    assert_eq!(
+      IngredientLine::parse_all("For the dough:"),
+      vec![line("", "For the dough:", "")]
+    );
+  }
+
+  #[test]
+  fn preview_keeps_plurals() {
+    let preview = IngredientLine::preview_table(&[line("2 cups", "Flour", "")]);
+    assert!(preview.ends_with("|2 cups|Flour||"), "{}", preview);
+  }
+}
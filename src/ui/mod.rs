@@ -1,3 +1,21 @@
+mod alternate_screen;
+mod cook_mode;
+mod ingredient_line;
+mod recipe_browser;
+mod recipe_document;
 mod recipe_editor;
+mod recipe_picker;
+mod tag_picker;
+mod template_picker;
+mod web_print;
 
+pub use alternate_screen::*;
+pub use cook_mode::*;
+pub use ingredient_line::*;
+pub use recipe_browser::*;
+pub use recipe_document::*;
 pub use recipe_editor::*;
+pub use recipe_picker::*;
+pub use tag_picker::*;
+pub use template_picker::*;
+pub use web_print::*;
@@ -0,0 +1,381 @@
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Print, Stylize};
+use crossterm::terminal::{self, Clear, ClearType};
+use crossterm::{cursor, queue};
+use diesel::{Connection, SqliteConnection};
+use inquire::MultiSelect;
+use itertools::Itertools;
+use std::io::{stdout, Write};
+use termimad::{Area, MadSkin, MadView};
+
+use super::{fit_width, fuzzy_filter, open_print_page, AlternateScreen, RecipeEditor};
+use crate::links::{LinkIndex, LinkStyle};
+use crate::models::{Recipe, SubstitutionPlan, Tag};
+use crate::subcommand;
+
+const HELP_TEXT: &str = "↑↓ select  / filter  e edit  p print  d delete  t tags  PgUp/PgDn scroll  q quit";
+
+/// Full-screen list of recipes with a preview of the selected recipe
+pub struct RecipeBrowser {
+  entries: Vec<BrowserEntry>,
+  filter: String,
+  mode: Mode,
+  selected: usize, // Index into the filtered entries
+  list_offset: usize,
+  preview: Option<(i32, MadView)>,
+  links: LinkIndex,
+  status: String,
+}
+
+struct BrowserEntry {
+  recipe: Recipe,
+  tags: Vec<Tag>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+  Browse,
+  Filter,
+  ConfirmDelete,
+}
+
+enum Action {
+  Continue,
+  Quit,
+  Edit(Recipe),
+  Print(Recipe),
+  SetTags(Recipe),
+}
+
+impl RecipeBrowser {
+  pub fn new(conn: &mut SqliteConnection) -> subcommand::Result<Self> {
+    Ok(Self {
+      entries: Self::load_entries(conn)?,
+      filter: String::new(),
+      mode: Mode::Browse,
+      selected: 0,
+      list_offset: 0,
+      preview: None,
+      links: LinkIndex::load(LinkStyle::Terminal, conn)?,
+      status: String::new(),
+    })
+  }
+
+  pub fn run(mut self, conn: &mut SqliteConnection) -> subcommand::Result<()> {
+    let screen = AlternateScreen::enter()?;
+
+    loop {
+      self.render(conn)?;
+
+      let key = match event::read()? {
+        Event::Key(key) if key.kind == KeyEventKind::Press => key,
+        _ => continue,
+      };
+
+      let result = match self.handle_key(key, conn)? {
+        Action::Continue => continue,
+        Action::Quit => break,
+
+        Action::Print(recipe) => open_print_page(&recipe, &SubstitutionPlan::default(), conn),
+
+        // Prompts need the normal terminal
+        Action::Edit(recipe) => {
+          screen.suspend()?;
+          let result = RecipeEditor::from_recipe(recipe, conn)
+            .map_err(Into::into)
+            .and_then(|e| e.resume_draft(conn))
+            .and_then(|e| e.edit(conn));
+          screen.resume()?;
+          result
+        },
+        Action::SetTags(recipe) => {
+          screen.suspend()?;
+          let result = Self::set_tags(&recipe, conn);
+          screen.resume()?;
+          result
+        },
+      };
+
+      // Errors (like cancelling a prompt) shouldn't close the browser
+      if let Err(e) = result {
+        self.status = format!("Error: {}", e);
+      }
+      self.reload(conn)?;
+    }
+
+    Ok(())
+  }
+
+  fn load_entries(conn: &mut SqliteConnection) -> subcommand::Result<Vec<BrowserEntry>> {
+    Recipe::all_ordered(conn)?
+      .into_iter()
+      .map(|recipe| {
+        let tags = recipe.get_tags_ordered(conn)?;
+        Ok(BrowserEntry { recipe, tags })
+      })
+      .collect()
+  }
+
+  /// Reload all recipes, keeping the same recipe selected if it still exists
+  fn reload(&mut self, conn: &mut SqliteConnection) -> subcommand::Result<()> {
+    let selected_id = self.selected_entry().map(|e| e.recipe.id);
+
+    self.entries = Self::load_entries(conn)?;
+    self.links = LinkIndex::load(LinkStyle::Terminal, conn)?;
+    self.preview = None;
+
+    if let Some(id) = selected_id {
+      if let Some(index) = self.filtered_entries().iter().position(|e| e.recipe.id == id) {
+        self.selected = index;
+      }
+    }
+    self.clamp_selection();
+
+    Ok(())
+  }
+
+  fn filtered_entries(&self) -> Vec<&BrowserEntry> {
+    self
+      .entries
+      .iter()
+      .filter(|e| fuzzy_filter(&self.filter, e, &e.search_text(), 0))
+      .collect()
+  }
+
+  fn selected_entry(&self) -> Option<&BrowserEntry> {
+    self.filtered_entries().get(self.selected).copied()
+  }
+
+  fn clamp_selection(&mut self) {
+    let count = self.filtered_entries().len();
+    self.selected = self.selected.min(count.saturating_sub(1));
+  }
+
+  fn handle_key(&mut self, key: KeyEvent, conn: &mut SqliteConnection) -> subcommand::Result<Action> {
+    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+      return Ok(Action::Quit);
+    }
+
+    match self.mode {
+      Mode::Filter => {
+        match key.code {
+          KeyCode::Char(c) => self.filter.push(c),
+          KeyCode::Backspace => {
+            self.filter.pop();
+          },
+          KeyCode::Esc => {
+            self.filter.clear();
+            self.mode = Mode::Browse;
+          },
+          KeyCode::Enter => self.mode = Mode::Browse,
+          _ => return Ok(self.handle_movement(key)),
+        }
+
+        self.selected = 0;
+        self.preview = None;
+        Ok(Action::Continue)
+      },
+
+      Mode::ConfirmDelete => {
+        self.mode = Mode::Browse;
+        if key.code != KeyCode::Char('y') {
+          self.status = "Delete cancelled".into();
+          return Ok(Action::Continue);
+        }
+
+        if let Some(entry) = self.selected_entry() {
+          let recipe = entry.recipe.clone();
+          self.status = match recipe.trash(conn) {
+            Ok(_) => format!("Moved \"{}\" to the trash", recipe.name),
+            Err(e) => format!("Error: {}", e),
+          };
+          self.reload(conn)?;
+        }
+        Ok(Action::Continue)
+      },
+
+      Mode::Browse => {
+        self.status.clear();
+        let selected_recipe = self.selected_entry().map(|e| e.recipe.clone());
+
+        match (key.code, selected_recipe) {
+          (KeyCode::Char('q') | KeyCode::Esc, _) => Ok(Action::Quit),
+          (KeyCode::Char('/'), _) => {
+            self.mode = Mode::Filter;
+            Ok(Action::Continue)
+          },
+          (KeyCode::Char('e') | KeyCode::Enter, Some(recipe)) => Ok(Action::Edit(recipe)),
+          (KeyCode::Char('p'), Some(recipe)) => Ok(Action::Print(recipe)),
+          (KeyCode::Char('t'), Some(recipe)) => Ok(Action::SetTags(recipe)),
+          (KeyCode::Char('d'), Some(_)) => {
+            self.mode = Mode::ConfirmDelete;
+            Ok(Action::Continue)
+          },
+          _ => Ok(self.handle_movement(key)),
+        }
+      },
+    }
+  }
+
+  fn handle_movement(&mut self, key: KeyEvent) -> Action {
+    let count = self.filtered_entries().len();
+    let old_selected = self.selected;
+
+    match key.code {
+      KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+      KeyCode::Down | KeyCode::Char('j') => self.selected += 1,
+      KeyCode::Home => self.selected = 0,
+      KeyCode::End => self.selected = count.saturating_sub(1),
+      KeyCode::PageUp => {
+        if let Some((_, view)) = self.preview.as_mut() {
+          view.try_scroll_pages(-1);
+        }
+      },
+      KeyCode::PageDown => {
+        if let Some((_, view)) = self.preview.as_mut() {
+          view.try_scroll_pages(1);
+        }
+      },
+      _ => {},
+    }
+
+    self.clamp_selection();
+    if self.selected != old_selected {
+      self.preview = None;
+    }
+
+    Action::Continue
+  }
+
+  fn set_tags(recipe: &Recipe, conn: &mut SqliteConnection) -> subcommand::Result<()> {
+    let all_tags = Tag::all_ordered(conn)?;
+    if all_tags.is_empty() {
+      return Err("No tags in the database".into());
+    }
+
+    let current_tags = recipe.get_tags(conn)?;
+    let selected_tags: Vec<_> = all_tags
+      .iter()
+      .enumerate()
+      .filter_map(|(index, tag)| current_tags.iter().any(|t| t.id == tag.id).then_some(index))
+      .collect();
+
+    let new_tags = MultiSelect::new(
+      &format!("Tags for \"{}\":", recipe.name),
+      all_tags.iter().map(|t| &t.name).collect(),
+    )
+    .with_default(&selected_tags)
+    .with_filter(&fuzzy_filter)
+    .raw_prompt_skippable()?;
+
+    if let Some(new_tags) = new_tags {
+      conn.transaction(|conn| recipe.set_tags_ids(new_tags.iter().map(|v| &all_tags[v.index].id), conn))?;
+    }
+
+    Ok(())
+  }
+
+  fn render(&mut self, conn: &mut SqliteConnection) -> subcommand::Result<()> {
+    let (width, height) = terminal::size()?;
+    let list_width = (width / 3).clamp(20, 40).min(width.saturating_sub(10));
+    let list_height = height.saturating_sub(2) as usize;
+
+    // Keep the selected recipe on screen
+    if self.selected < self.list_offset {
+      self.list_offset = self.selected;
+    } else if list_height > 0 && self.selected >= self.list_offset + list_height {
+      self.list_offset = self.selected + 1 - list_height;
+    }
+
+    let mut out = stdout();
+    queue!(out, Clear(ClearType::All))?;
+
+    // Header line above the list
+    let header = match self.mode {
+      Mode::Filter => format!("/{}_", self.filter),
+      _ if !self.filter.is_empty() => format!("Filter: {}", self.filter),
+      _ => format!("Recipes ({})", self.entries.len()),
+    };
+    queue!(out, cursor::MoveTo(0, 0), Print(fit_width(&header, list_width).bold()))?;
+
+    let filtered_entries = self.filtered_entries();
+    for (row, (index, entry)) in filtered_entries
+      .iter()
+      .enumerate()
+      .skip(self.list_offset)
+      .take(list_height)
+      .enumerate()
+    {
+      let line = fit_width(&entry.recipe.name, list_width);
+      queue!(out, cursor::MoveTo(0, row as u16 + 1))?;
+      if index == self.selected {
+        queue!(out, Print(line.reverse()))?;
+      } else {
+        queue!(out, Print(line))?;
+      }
+    }
+
+    for row in 0..height.saturating_sub(1) {
+      queue!(out, cursor::MoveTo(list_width, row), Print("│".dark_grey()))?;
+    }
+
+    // Footer line with either the status message or help text
+    let footer = match self.mode {
+      Mode::ConfirmDelete => format!(
+        "Move \"{}\" to the trash? (y/n)",
+        self
+          .selected_entry()
+          .map(|e| e.recipe.name.as_str())
+          .unwrap_or_default()
+      ),
+      _ if !self.status.is_empty() => self.status.clone(),
+      _ => HELP_TEXT.into(),
+    };
+    queue!(
+      out,
+      cursor::MoveTo(0, height.saturating_sub(1)),
+      Print(fit_width(&footer, width).dark_grey())
+    )?;
+
+    // Markdown preview of the selected recipe
+    let preview_area = Area::new(
+      list_width + 2,
+      0,
+      width.saturating_sub(list_width + 2),
+      height.saturating_sub(1),
+    );
+    let selected_recipe = filtered_entries.get(self.selected).map(|e| e.recipe.clone());
+    match selected_recipe {
+      None => self.preview = None,
+      Some(recipe) => {
+        if self.preview.as_ref().map(|(id, _)| *id) != Some(recipe.id) {
+          let markdown = recipe.markdown_string(&self.links, conn)?;
+          self.preview = Some((
+            recipe.id,
+            MadView::from(markdown, preview_area.clone(), MadSkin::default()),
+          ));
+        }
+      },
+    }
+
+    // Termimad can't render into an empty area
+    if let Some((_, view)) = self
+      .preview
+      .as_mut()
+      .filter(|_| preview_area.width > 1 && preview_area.height > 0)
+    {
+      view.resize(&preview_area);
+      view.write_on(&mut out)?;
+    }
+
+    out.flush()?;
+    Ok(())
+  }
+}
+
+impl BrowserEntry {
+  /// Text matched against the filter
+  fn search_text(&self) -> String {
+    format!("{} {}", self.recipe.name, self.tags.iter().map(|t| &t.name).join(" "))
+  }
+}
@@ -0,0 +1,337 @@
+use itertools::Itertools;
+use serde::Deserialize;
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::{self, stdin, Read};
+use std::path::Path;
+
+use super::IngredientLine;
+use crate::models::Tag;
+
+const FRONT_MATTER_DELIMITER: &str = "---";
+const ERROR_COMMENT_START: &str = "<!-- Error:";
+
+/// Entire recipe written as a single markdown file with YAML front matter
+///
+/// Recipes have no metadata besides the name and tags, so those are the only front matter fields.
+///
+/// ```md
+/// ---
+/// name: Pancakes
+/// tags: breakfast, quick
+/// ---
+///
+/// ## Ingredients
+/// 1 Cup: Flour
+/// - Sifted
+///
+/// ## Instructions
+/// ...
+///
+/// ## Notes
+/// ...
+/// ```
+///
+/// The same fields can also be given as JSON:
+///
+/// ```json
+/// {
+///   "name": "Pancakes",
+///   "tags": ["breakfast", "quick"],
+///   "ingredients": [{ "quantity": "1 Cup", "name": "Flour", "notes": "Sifted" }],
+///   "instructions": "...",
+///   "notes": "..."
+/// }
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RecipeDocument {
+  pub name: String,
+  #[serde(default)]
+  pub tags: Vec<String>, // Tag IDs
+  #[serde(default)]
+  pub ingredients: Vec<IngredientLine>,
+  #[serde(default, rename = "instructions")]
+  pub instructions_markdown: String,
+  #[serde(default, rename = "notes")]
+  pub notes_markdown: String,
+}
+
+/// YAML between the front matter delimiters
+///
+/// Recipes have no metadata besides their name and tags, so any other key is rejected rather than silently dropped.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FrontMatter {
+  #[serde(default)]
+  name: Option<String>,
+  #[serde(default)]
+  tags: Option<TagList>,
+}
+
+/// Tags as either a YAML list or a comma-separated string like "breakfast, quick"
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum TagList {
+  List(Vec<String>),
+  Text(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+  Ingredients,
+  Instructions,
+  Notes,
+}
+
+impl RecipeDocument {
+  /// Read either a markdown or JSON document from a file, or from stdin if the path is "-"
+  pub fn read_from(path: &Path) -> io::Result<String> {
+    if path == Path::new("-") {
+      let mut input = String::new();
+      stdin().read_to_string(&mut input)?;
+      Ok(input)
+    } else {
+      fs::read_to_string(path)
+    }
+  }
+
+  /// Parse JSON if the input looks like a JSON object, otherwise parse markdown
+  pub fn parse_any(input: &str) -> Result<Self, String> {
+    if !input.trim_start().starts_with('{') {
+      return Self::parse(input);
+    }
+
+    let document: Self = serde_json::from_str(input).map_err(|e| format!("Invalid JSON: {}", e))?;
+    if document.name.trim().is_empty() {
+      return Err("Recipe name cannot be empty".into());
+    }
+    if document.ingredients.iter().any(|i| i.name.trim().is_empty()) {
+      return Err("Ingredient name cannot be empty".into());
+    }
+    check_duplicate_ingredients(&document.ingredients)?;
+
+    Ok(document)
+  }
+
+  pub fn parse(input: &str) -> Result<Self, String> {
+    let mut lines = input
+      .lines()
+      .filter(|line| !line.trim_start().starts_with(ERROR_COMMENT_START))
+      .skip_while(|line| line.trim().is_empty());
+
+    // Front matter
+    if lines.next().map(str::trim) != Some(FRONT_MATTER_DELIMITER) {
+      return Err(format!(
+        "Document must start with front matter between \"{0}\" lines",
+        FRONT_MATTER_DELIMITER
+      ));
+    }
+
+    let mut front_matter_lines = Vec::new();
+    let mut found_end = false;
+    for line in lines.by_ref() {
+      if line.trim() == FRONT_MATTER_DELIMITER {
+        found_end = true;
+        break;
+      }
+      front_matter_lines.push(line);
+    }
+
+    if !found_end {
+      return Err(format!(
+        "Front matter is missing the closing \"{}\" line",
+        FRONT_MATTER_DELIMITER
+      ));
+    }
+
+    let front_matter = FrontMatter::parse(&front_matter_lines.join("\n"))?;
+    let name = front_matter.name.unwrap_or_default().trim().to_string();
+    if name.is_empty() {
+      return Err("Recipe name cannot be empty".into());
+    }
+    let tags = front_matter.tags.map(TagList::into_vec).unwrap_or_default();
+
+    // Markdown sections
+    let mut current_section = None;
+    let mut sections: Vec<(Section, Vec<&str>)> = Vec::new();
+    for line in lines {
+      if let Some(section) = Section::from_heading(line) {
+        if sections.iter().any(|(s, _)| *s == section) {
+          return Err(format!("Section \"{}\" appears more than once", line.trim()));
+        }
+        sections.push((section, Vec::new()));
+        current_section = Some(section);
+        continue;
+      }
+
+      match (current_section, sections.last_mut()) {
+        (Some(_), Some((_, section_lines))) => section_lines.push(line),
+        _ if line.trim().is_empty() => {},
+        _ => return Err(format!("Text must be inside a section: {}", line.trim())),
+      }
+    }
+
+    let get_section = |section: Section| {
+      sections
+        .iter()
+        .find(|(s, _)| *s == section)
+        .map(|(_, lines)| lines.join("\n").trim().to_string())
+        .unwrap_or_default()
+    };
+
+    let ingredients = IngredientLine::parse_all(&get_section(Section::Ingredients));
+    check_duplicate_ingredients(&ingredients)?;
+
+    Ok(Self {
+      name,
+      tags,
+      ingredients,
+      instructions_markdown: get_section(Section::Instructions),
+      notes_markdown: get_section(Section::Notes),
+    })
+  }
+
+  /// Write the document, listing all tags that could be used in a comment
+  pub fn to_markdown(&self, all_tags: &[Tag]) -> String {
+    let tags_comment = if !all_tags.is_empty() {
+      format!("\n# Available tags: {}", all_tags.iter().map(|t| &t.id).join(", "))
+    } else {
+      "".into()
+    };
+
+    format!(
+      "{delimiter}\nname: {}\ntags: {}{}\n{delimiter}\n\n## Ingredients\n{}\n\n## Instructions\n{}\n\n## Notes\n{}\n",
+      yaml_string(&self.name),
+      self.tags.join(", "),
+      tags_comment,
+      IngredientLine::format_all(&self.ingredients),
+      self.instructions_markdown.trim(),
+      self.notes_markdown.trim(),
+      delimiter = FRONT_MATTER_DELIMITER,
+    )
+  }
+
+  /// Put the error message at the top of the document so it shows up when the editor is reopened
+  pub fn with_error(input: &str, error: &str) -> String {
+    let input = input
+      .lines()
+      .filter(|line| !line.trim_start().starts_with(ERROR_COMMENT_START))
+      .join("\n");
+
+    format!(
+      "{} {} -->\n{}",
+      ERROR_COMMENT_START,
+      error.replace("-->", "").replace('\n', " "),
+      input
+    )
+  }
+}
+
+impl FrontMatter {
+  fn parse(yaml: &str) -> Result<Self, String> {
+    // An empty or comment-only front matter is null in YAML
+    match serde_yaml::from_str::<Option<Self>>(yaml) {
+      Ok(front_matter) => Ok(front_matter.unwrap_or_default()),
+      Err(e) => Err(format!("Invalid front matter: {}", e)),
+    }
+  }
+}
+
+impl TagList {
+  fn into_vec(self) -> Vec<String> {
+    let tags = match self {
+      TagList::List(tags) => tags,
+      TagList::Text(text) => text.split(',').map(String::from).collect(),
+    };
+    tags
+      .into_iter()
+      .map(|tag| tag.trim().to_string())
+      .filter(|tag| !tag.is_empty())
+      .collect()
+  }
+}
+
+impl Section {
+  fn from_heading(line: &str) -> Option<Self> {
+    let heading = line.trim().strip_prefix("## ")?.trim().to_lowercase();
+    match heading.as_str() {
+      "ingredients" => Some(Section::Ingredients),
+      "instructions" => Some(Section::Instructions),
+      "notes" => Some(Section::Notes),
+      _ => None,
+    }
+  }
+}
+
+fn check_duplicate_ingredients(ingredients: &[IngredientLine]) -> Result<(), String> {
+  let mut all_ingredients = BTreeSet::new();
+  match ingredients.iter().find(|i| !all_ingredients.insert(i.name.as_str())) {
+    Some(duplicate) => Err(format!("Duplicate ingredient \"{}\"", duplicate.name)),
+    None => Ok(()),
+  }
+}
+
+/// Write a string as a YAML scalar, quoting it only if it would otherwise be read back differently
+///
+/// An empty string is left blank, so a new recipe's name can be typed straight after "name: ".
+fn yaml_string(value: &str) -> String {
+  if value.is_empty() {
+    return String::new();
+  }
+  serde_yaml::to_string(value)
+    .map(|yaml| yaml.trim_end().to_string())
+    .unwrap_or_else(|_| format!("{:?}", value))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn yaml_front_matter() {
+    let document =
+      RecipeDocument::parse("---\nname: \"Mac: Cheese\"\ntags:\n  - dinner\n  - quick\n---\n## Instructions\nBake")
+        .unwrap();
+    assert_eq!(document.name, "Mac: Cheese");
+    assert_eq!(document.tags, vec!["dinner", "quick"]);
+    assert_eq!(document.instructions_markdown, "Bake");
+
+    let document = RecipeDocument::parse("---\nname: Toast # Comment\ntags: breakfast, quick\n---\n").unwrap();
+    assert_eq!(document.name, "Toast");
+    assert_eq!(document.tags, vec!["breakfast", "quick"]);
+  }
+
+  #[test]
+  fn front_matter_errors() {
+    assert!(RecipeDocument::parse("---\nname: Toast\nservings: 2\n---\n")
+      .unwrap_err()
+      .contains("unknown field `servings`"));
+    assert_eq!(
+      RecipeDocument::parse("---\ntags: quick\n---\n").unwrap_err(),
+      "Recipe name cannot be empty"
+    );
+  }
+
+  #[test]
+  fn json_rejects_blank_ingredient_names() {
+    assert_eq!(
+      RecipeDocument::parse_any(r#"{"name": "Toast", "ingredients": [{"name": " "}]}"#).unwrap_err(),
+      "Ingredient name cannot be empty"
+    );
+  }
+
+  #[test]
+  fn name_round_trip() {
+    for name in ["Pancakes", "Mac: Cheese", "123", "true", "#1 Chili"] {
+      let document = RecipeDocument {
+        name: name.to_string(),
+        tags: Vec::new(),
+        ingredients: Vec::new(),
+        instructions_markdown: String::new(),
+        notes_markdown: String::new(),
+      };
+      assert_eq!(RecipeDocument::parse(&document.to_markdown(&[])).unwrap().name, name);
+    }
+  }
+}
@@ -1,20 +1,30 @@
+use clap::ValueEnum;
 use crossterm::style::Stylize;
 use diesel::prelude::*;
 use diesel::SqliteConnection;
 use inquire::validator::ValueRequiredValidator;
 use inquire::{Confirm, Editor, MultiSelect, Select, Text};
 use itertools::Itertools;
+use serde::{Deserialize, Serialize};
 use std::collections::BTreeSet;
+use std::fmt;
+use std::io::{stdin, IsTerminal};
 use std::iter;
 use termimad::MadSkin;
 
+use super::{IngredientLine, RecipeDocument};
 use crate::models::IngredientConstructor;
-use crate::models::{Ingredient, Recipe, RecipeConstructor, RecipeIngredient, Tag};
+use crate::models::{Ingredient, IngredientCategory, Recipe, RecipeConstructor, RecipeIngredient, RecipeRevision, Tag};
+use crate::models::{RecipeDraft, RecipeDraftConstructor, RecipeTemplate};
+use crate::name_match::AUTO_ACCEPT_SCORE;
 use crate::subcommand;
 
 const PLACEHOLDER_INGREDIENTS: &str = include_str!("placeholder-ingredients.txt");
 const PLACEHOLDER_INSTRUCTIONS: &str = include_str!("placeholder-instructions.md");
 
+/// The fractions cheat sheet would be read as ingredients in a document, so it's left out
+const PLACEHOLDER_DOCUMENT_INGREDIENTS: &str = include_str!("placeholder-document-ingredients.txt");
+
 #[derive(Debug, Clone)]
 pub struct RecipeEditor {
   existing_recipe_id: Option<i32>,
@@ -24,16 +34,66 @@ pub struct RecipeEditor {
   notes_markdown: String,
   all_tags: Vec<Tag>,
   selected_tags: Vec<TagIndex>,
+  draft: Option<RecipeDraft>,           // Autosaved after every step
+  template_ingredients: Option<String>, // Shown instead of the placeholder until ingredients are entered
 }
 
 type TagIndex = usize;
 
-#[derive(Debug, Clone)]
+/// Part of the recipe that can be edited on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditorSection {
+  Name,
+  Ingredients,
+  Instructions,
+  Notes,
+  Tags,
+}
+
+/// What to do with ingredients that aren't in the database when importing a recipe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum NewIngredients {
+  /// Add the ingredient to the database
+  Create,
+  /// Stop with an error
+  Fail,
+}
+
+/// Entries in the editor menu
+#[derive(Debug, Clone, Copy)]
+enum MenuOption {
+  Save,
+  Edit(EditorSection),
+  Cancel,
+}
+
+/// Entries in the prompt to resume a draft of a new recipe
+enum DraftOption {
+  New,
+  Resume(RecipeDraft),
+}
+
+/// Entries in the prompt to pick a category for a new ingredient
+struct CategoryOption<'a>(Option<&'a IngredientCategory>);
+
+/// Editor state stored as JSON in a draft
+#[derive(Debug, Serialize, Deserialize)]
+struct DraftState {
+  name: String,
+  ingredients: Vec<IngredientEntry>,
+  instructions_markdown: String,
+  notes_markdown: String,
+  tags: Vec<String>, // Tag IDs
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct IngredientEntry {
   ingredient_id: Option<i32>, // If None, will create a new ingredient
   name: String,
   quantity: String,
   notes_markdown: String,
+  #[serde(default)]
+  category_id: Option<String>, // Only used when creating a new ingredient
 }
 
 impl RecipeEditor {
@@ -48,9 +108,27 @@ impl RecipeEditor {
       notes_markdown: String::new(),
       all_tags,
       selected_tags: Vec::new(),
+      draft: None,
+      template_ingredients: None,
     })
   }
 
+  /// Start a new recipe from a template instead of the placeholders
+  pub fn with_template(mut self, template: &RecipeTemplate, conn: &mut SqliteConnection) -> QueryResult<Self> {
+    let template_tags = template.get_tags(conn)?;
+    self.selected_tags = self
+      .all_tags
+      .iter()
+      .enumerate()
+      .filter_map(|(index, tag)| template_tags.iter().any(|t| t.id == tag.id).then_some(index))
+      .collect();
+
+    self.template_ingredients = Some(template.ingredients_text.clone());
+    self.instructions_markdown = template.instructions_markdown.clone();
+    self.notes_markdown = template.notes_markdown.clone();
+    Ok(self)
+  }
+
   pub fn from_recipe(recipe: Recipe, conn: &mut SqliteConnection) -> QueryResult<Self> {
     let loaded_ingredients = recipe
       .get_ingredients_with_metadata(conn)?
@@ -60,10 +138,17 @@ impl RecipeEditor {
         name: i.name,
         quantity: ri.quantity,
         notes_markdown: ri.notes_markdown,
+        category_id: None,
       })
       .collect();
 
     let all_tags = Tag::all_ordered(conn)?;
+    let recipe_tags = recipe.get_tags(conn)?;
+    let selected_tags = all_tags
+      .iter()
+      .enumerate()
+      .filter_map(|(index, tag)| recipe_tags.iter().any(|t| t.id == tag.id).then_some(index))
+      .collect();
 
     Ok(Self {
       existing_recipe_id: Some(recipe.id),
@@ -72,61 +157,279 @@ impl RecipeEditor {
       instructions_markdown: recipe.instructions_markdown,
       notes_markdown: recipe.notes_markdown,
       all_tags,
-      selected_tags: Vec::new(),
+      selected_tags,
+      draft: None,
+      template_ingredients: None,
     })
   }
 
-  pub fn edit(mut self, conn: &mut SqliteConnection) -> subcommand::Result<()> {
-    loop {
-      self.name = Text::new("Recipe Name:")
-        .with_initial_value(&self.name)
-        .with_validator(ValueRequiredValidator::new("Recipe name cannot be empty"))
-        .prompt()?;
+  /// Offer to pick up where a previous editor session left off
+  pub fn resume_draft(mut self, conn: &mut SqliteConnection) -> subcommand::Result<Self> {
+    let mut drafts = RecipeDraft::find_for_recipe(self.existing_recipe_id, conn)?;
+    if drafts.is_empty() || !stdin().is_terminal() {
+      return Ok(self);
+    }
 
-      loop {
-        let ingredients_string = Editor::new("Ingredients")
-          .with_file_extension(".md")
-          .with_predefined_text(&self.get_ingredients_text())
-          .prompt()?;
+    let selected_option = if self.existing_recipe_id.is_some() {
+      let draft = drafts.remove(0);
+      if Confirm::new(&format!("Resume unsaved changes from {}? (Y/N)", draft.updated_at)).prompt()? {
+        DraftOption::Resume(draft)
+      } else {
+        // Only one draft is kept per recipe, so it gets overwritten by this session
+        self.draft = Some(draft);
+        DraftOption::New
+      }
+    } else {
+      Select::new(
+        "Resume an unfinished recipe?",
+        iter::once(DraftOption::New)
+          .chain(drafts.into_iter().map(DraftOption::Resume))
+          .collect(),
+      )
+      .prompt()?
+    };
 
-        self.parse_and_set_ingredients_from_str(&ingredients_string, conn)?;
+    if let DraftOption::Resume(draft) = selected_option {
+      self.load_draft(draft, conn)?;
+    }
 
-        // Special case: make sure we didn't specify the same ingredient twice
-        let duplicate_ingredients = self.get_duplicate_ingredients();
-        if duplicate_ingredients.is_empty() {
-          break;
+    Ok(self)
+  }
+
+  fn load_draft(&mut self, draft: RecipeDraft, conn: &mut SqliteConnection) -> subcommand::Result<()> {
+    let state: DraftState = serde_json::from_str(&draft.editor_state)?;
+
+    self.name = state.name;
+    self.instructions_markdown = state.instructions_markdown;
+    self.notes_markdown = state.notes_markdown;
+
+    // Tags or ingredients might have been deleted since the draft was saved
+    self.selected_tags = state
+      .tags
+      .iter()
+      .filter_map(|id| self.all_tags.iter().position(|t| &t.id == id))
+      .collect();
+
+    self.ingredients = state.ingredients;
+    for ingredient in self.ingredients.iter_mut() {
+      if let Some(id) = ingredient.ingredient_id {
+        if !Ingredient::exists_from_id(&id, conn)? {
+          ingredient.ingredient_id = None;
         }
+      }
+    }
 
-        println!("{}: duplicate ingredients found in list", "Error".red());
-        let skin = MadSkin::default();
-        skin.print_text(&duplicate_ingredients.iter().map(|i| format!("- {}", i)).join("\n"));
+    self.draft = Some(draft);
+    Ok(())
+  }
+
+  fn save_draft(&mut self, conn: &mut SqliteConnection) -> subcommand::Result<()> {
+    let editor_state = serde_json::to_string(&DraftState {
+      name: self.name.clone(),
+      ingredients: self.ingredients.clone(),
+      instructions_markdown: self.instructions_markdown.clone(),
+      notes_markdown: self.notes_markdown.clone(),
+      tags: self
+        .selected_tags
+        .iter()
+        .map(|index| self.all_tags[*index].id.clone())
+        .collect(),
+    })?;
+
+    self.draft = Some(match self.draft.as_ref() {
+      Some(draft) => draft.update_state(&self.name, &editor_state, conn)?,
+      None => RecipeDraftConstructor {
+        recipe_id: self.existing_recipe_id,
+        name: &self.name,
+        editor_state: &editor_state,
       }
+      .insert_recipe_draft(conn)?,
+    });
 
-      self.instructions_markdown = Editor::new("Instructions")
-        .with_file_extension(".md")
-        .with_predefined_text(&self.instructions_markdown)
-        .prompt()?;
+    Ok(())
+  }
+
+  fn discard_draft(&self, conn: &mut SqliteConnection) -> QueryResult<()> {
+    if let Some(draft) = self.draft.as_ref() {
+      draft.delete(conn)?;
+    }
+    Ok(())
+  }
 
-      self.notes_markdown = Editor::new("Notes")
+  /// Edit the recipe, starting with every section in order for new recipes
+  pub fn edit(self, conn: &mut SqliteConnection) -> subcommand::Result<()> {
+    self.run(None, conn)
+  }
+
+  /// Edit one section of the recipe before showing the menu
+  pub fn edit_section(self, section: EditorSection, conn: &mut SqliteConnection) -> subcommand::Result<()> {
+    self.run(Some(section), conn)
+  }
+
+  fn run(mut self, start_section: Option<EditorSection>, conn: &mut SqliteConnection) -> subcommand::Result<()> {
+    match start_section {
+      Some(section) => {
+        self.edit_one_section(section, conn)?;
+        self.save_draft(conn)?;
+      },
+      None if self.existing_recipe_id.is_none() && self.draft.is_none() => {
+        for section in EditorSection::ALL {
+          self.edit_one_section(section, conn)?;
+          self.save_draft(conn)?;
+        }
+      },
+      None => {},
+    }
+
+    loop {
+      self.print_current_state();
+
+      let mut options = vec![MenuOption::Save];
+      options.extend(
+        EditorSection::ALL
+          .into_iter()
+          .filter(|s| *s != EditorSection::Tags || !self.all_tags.is_empty())
+          .map(MenuOption::Edit),
+      );
+      options.push(MenuOption::Cancel);
+
+      match Select::new("What would you like to do?", options).prompt()? {
+        MenuOption::Save => break,
+        MenuOption::Edit(section) => {
+          self.edit_one_section(section, conn)?;
+          self.save_draft(conn)?;
+        },
+        MenuOption::Cancel => {
+          if Confirm::new("Really discard all changes? (Y/N)").prompt()? {
+            self.discard_draft(conn)?;
+            println!("No changes made");
+            return Ok(());
+          }
+        },
+      }
+    }
+
+    self.categorize_new_ingredients(conn)?;
+    self.save(conn)
+  }
+
+  /// Edit the whole recipe as one markdown document, reopening the editor on any errors
+  pub fn edit_document(mut self, conn: &mut SqliteConnection) -> subcommand::Result<()> {
+    let mut text = self.get_document().to_markdown(&self.all_tags);
+    loop {
+      text = Editor::new("Recipe")
         .with_file_extension(".md")
-        .with_predefined_text(&self.notes_markdown)
+        .with_predefined_text(&text)
         .prompt()?;
 
-      if !self.all_tags.is_empty() {
-        self.selected_tags = MultiSelect::new("Recipe Tags:", self.all_tags.iter().map(|t| &t.name).collect())
-          .with_default(&self.selected_tags)
-          .raw_prompt()?
-          .into_iter()
-          .map(|v| v.index)
-          .collect();
+      let result = RecipeDocument::parse(&text).and_then(|document| {
+        let tag_indices = self.find_tag_indices(&document.tags)?;
+        Ok((document, tag_indices))
+      });
+
+      match result {
+        Ok((document, tag_indices)) => {
+          self.name = document.name;
+          self.instructions_markdown = document.instructions_markdown;
+          self.notes_markdown = document.notes_markdown;
+          self.selected_tags = tag_indices;
+          self.set_ingredients(document.ingredients, conn)?;
+          self.save_draft(conn)?;
+
+          // Different names can match the same ingredient, like "egg" and "Eggs"
+          match self.check_duplicate_ingredients() {
+            Ok(()) => break,
+            Err(e) => {
+              println!("{}: {}", "Error".red(), e);
+              text = RecipeDocument::with_error(&text, &e);
+            },
+          }
+        },
+
+        Err(e) => {
+          println!("{}: {}", "Error".red(), e);
+          text = RecipeDocument::with_error(&text, &e);
+        },
       }
+    }
 
-      self.print_current_state();
-      if Confirm::new("Does everything look good? (Y/N)").prompt()? {
-        break;
+    self.print_current_state();
+    self.categorize_new_ingredients(conn)?;
+    self.save(conn)
+  }
+
+  /// Replace the recipe with the document and save it without any prompts
+  pub fn import_document(
+    mut self,
+    document: RecipeDocument,
+    new_ingredients: NewIngredients,
+    conn: &mut SqliteConnection,
+  ) -> subcommand::Result<()> {
+    self.selected_tags = self.find_tag_indices(&document.tags)?;
+    self.name = document.name;
+    self.instructions_markdown = document.instructions_markdown;
+    self.notes_markdown = document.notes_markdown;
+
+    self.ingredients = Vec::new();
+    for line in document.ingredients {
+      let existing_ingredient = Ingredient::find_similar(&line.name, conn)?
+        .into_iter()
+        .next()
+        .filter(|(_, score)| *score >= AUTO_ACCEPT_SCORE)
+        .map(|(ingredient, _)| ingredient);
+
+      if existing_ingredient.is_none() && new_ingredients == NewIngredients::Fail {
+        return Err(format!("Unknown ingredient \"{}\"", line.name).into());
       }
+
+      self.ingredients.push(IngredientEntry {
+        ingredient_id: existing_ingredient.as_ref().map(|i| i.id),
+        name: existing_ingredient.map(|i| i.name).unwrap_or(line.name),
+        quantity: line.quantity,
+        notes_markdown: line.notes_markdown,
+        category_id: None,
+      });
+    }
+    self.check_duplicate_ingredients()?;
+
+    self.save(conn)
+  }
+
+  /// Ask which store section each new ingredient belongs to
+  fn categorize_new_ingredients(&mut self, conn: &mut SqliteConnection) -> subcommand::Result<()> {
+    let all_categories = IngredientCategory::all_ordered(conn)?;
+    if all_categories.is_empty() {
+      return Ok(());
+    }
+
+    let new_indices = (0..self.ingredients.len())
+      .filter(|&i| self.ingredients[i].ingredient_id.is_none())
+      .collect_vec();
+    for index in new_indices {
+      let ingredient = &mut self.ingredients[index];
+      let starting_cursor = ingredient
+        .category_id
+        .as_ref()
+        .and_then(|id| all_categories.iter().position(|c| &c.id == id))
+        .map_or(0, |index| index + 1);
+
+      let selected_category = Select::new(
+        &format!("Category for new ingredient \"{}\":", ingredient.name),
+        iter::once(CategoryOption(None))
+          .chain(all_categories.iter().map(|c| CategoryOption(Some(c))))
+          .collect(),
+      )
+      .with_starting_cursor(starting_cursor)
+      .prompt_skippable()?;
+
+      ingredient.category_id = selected_category.and_then(|option| option.0).map(|c| c.id.clone());
+      self.save_draft(conn)?;
     }
 
+    Ok(())
+  }
+
+  fn save(self, conn: &mut SqliteConnection) -> subcommand::Result<()> {
     if self.existing_recipe_id.is_some() {
       self.update_recipe(conn)?;
       println!("Changes saved!");
@@ -138,66 +441,197 @@ impl RecipeEditor {
     Ok(())
   }
 
+  fn get_document(&self) -> RecipeDocument {
+    RecipeDocument {
+      name: self.name.clone(),
+      tags: self
+        .selected_tags
+        .iter()
+        .map(|index| self.all_tags[*index].id.clone())
+        .collect(),
+      ingredients: if self.ingredients.is_empty() {
+        IngredientLine::parse_all(
+          self
+            .template_ingredients
+            .as_deref()
+            .unwrap_or(PLACEHOLDER_DOCUMENT_INGREDIENTS),
+        )
+      } else {
+        self.get_ingredient_lines()
+      },
+      instructions_markdown: self.instructions_markdown.clone(),
+      notes_markdown: self.notes_markdown.clone(),
+    }
+  }
+
+  fn find_tag_indices(&self, tag_ids: &[String]) -> Result<Vec<TagIndex>, String> {
+    tag_ids
+      .iter()
+      .map(|id| {
+        self
+          .all_tags
+          .iter()
+          .position(|t| &t.id == id)
+          .ok_or_else(|| format!("No such tag: {}", id))
+      })
+      .collect()
+  }
+
+  fn edit_one_section(&mut self, section: EditorSection, conn: &mut SqliteConnection) -> subcommand::Result<()> {
+    match section {
+      EditorSection::Name => {
+        self.name = Text::new("Recipe Name:")
+          .with_initial_value(&self.name)
+          .with_validator(ValueRequiredValidator::new("Recipe name cannot be empty"))
+          .prompt()?;
+      },
+
+      EditorSection::Ingredients => loop {
+        let ingredients_string = Editor::new("Ingredients")
+          .with_file_extension(".md")
+          .with_predefined_text(&self.get_ingredients_text())
+          .prompt()?;
+
+        self.parse_and_set_ingredients_from_str(&ingredients_string, conn)?;
+
+        // Special case: make sure we didn't specify the same ingredient twice
+        let duplicate_ingredients = self.get_duplicate_ingredients();
+        if duplicate_ingredients.is_empty() {
+          break;
+        }
+
+        println!("{}: duplicate ingredients found in list", "Error".red());
+        let skin = MadSkin::default();
+        skin.print_text(&duplicate_ingredients.iter().map(|i| format!("- {}", i)).join("\n"));
+      },
+
+      EditorSection::Instructions => {
+        self.instructions_markdown = Editor::new("Instructions")
+          .with_file_extension(".md")
+          .with_predefined_text(&self.instructions_markdown)
+          .prompt()?;
+      },
+
+      EditorSection::Notes => {
+        self.notes_markdown = Editor::new("Notes")
+          .with_file_extension(".md")
+          .with_predefined_text(&self.notes_markdown)
+          .prompt()?;
+      },
+
+      EditorSection::Tags => {
+        if !self.all_tags.is_empty() {
+          self.selected_tags = MultiSelect::new("Recipe Tags:", self.all_tags.iter().map(|t| &t.name).collect())
+            .with_default(&self.selected_tags)
+            .raw_prompt()?
+            .into_iter()
+            .map(|v| v.index)
+            .collect();
+        }
+      },
+    }
+
+    Ok(())
+  }
+
   fn get_ingredients_text(&self) -> String {
     if self.ingredients.is_empty() {
-      return PLACEHOLDER_INGREDIENTS.into();
+      return self
+        .template_ingredients
+        .as_deref()
+        .unwrap_or(PLACEHOLDER_INGREDIENTS)
+        .into();
     }
 
+    IngredientLine::format_all(&self.get_ingredient_lines())
+  }
+
+  fn get_ingredient_lines(&self) -> Vec<IngredientLine> {
     self
       .ingredients
       .iter()
-      .map(|i| {
-        if !i.notes_markdown.is_empty() {
-          format!("{}: {}\n- {}", i.quantity, i.name, i.notes_markdown)
-        } else {
-          format!("{}: {}", i.quantity, i.name)
-        }
+      .map(|i| IngredientLine {
+        quantity: i.quantity.clone(),
+        name: i.name.clone(),
+        notes_markdown: i.notes_markdown.clone(),
       })
-      .join("\n")
+      .collect()
   }
 
-  /// Prompts the user to select the matching ingredients one-by-one
   fn parse_and_set_ingredients_from_str(&mut self, input: &str, conn: &mut SqliteConnection) -> subcommand::Result<()> {
+    self.set_ingredients(IngredientLine::parse_all(input), conn)
+  }
+
+  /// Prompts the user to select the matching ingredients one-by-one
+  ///
+  /// Names that are the same except for case or plurals are matched without asking.
+  fn set_ingredients(&mut self, lines: Vec<IngredientLine>, conn: &mut SqliteConnection) -> subcommand::Result<()> {
     self.ingredients = Vec::new();
 
-    let mut lines = input.lines().map(|s| s.trim()).filter(|s| !s.is_empty()).peekable();
-    while let Some(line) = lines.next() {
-      let (quantity, ingredient) = line.split_once(":").unwrap_or(("", line));
-      let (quantity, ingredient) = (quantity.trim(), ingredient.trim());
+    let skin = MadSkin::default();
+    skin.print_text(&format!(
+      "**Ingredients as read:**\n{}",
+      IngredientLine::preview_table(&lines)
+    ));
 
-      // Next line is notes if it starts with "- "
-      let notes = match lines.peek() {
-        Some(next_line) if next_line.starts_with("- ") => lines.next().unwrap().strip_prefix("- ").unwrap(),
-        _ => "",
+    for line in lines {
+      let ingredient = line.name.as_str();
+
+      let matching_ingredients = Ingredient::find_similar(ingredient, conn)?;
+      let selected_ingredient = match matching_ingredients.first() {
+        Some((best_match, score)) if *score >= AUTO_ACCEPT_SCORE => {
+          if best_match.name != ingredient {
+            println!(
+              "{}",
+              format!("Using \"{}\" for \"{}\"", best_match.name, ingredient).dark_grey()
+            );
+          }
+          1
+        },
+
+        // Start on the best match, if there is one
+        _ => {
+          Select::new(
+            &format!("Unknown ingredient \"{ingredient}\""),
+            iter::once("<New Ingredient>")
+              .chain(matching_ingredients.iter().map(|(i, _)| i.name.as_str()))
+              .collect(),
+          )
+          .with_starting_cursor(matching_ingredients.len().min(1))
+          .raw_prompt()?
+          .index
+        },
       };
 
-      let matching_ingredients = Ingredient::find_by_name(ingredient, conn)?;
-      let selected_ingredient = if let Some(index) = matching_ingredients.iter().position(|i| i.name == ingredient) {
-        // Name matches exactly, use that ingredient
-        index + 1
-      } else {
-        Select::new(
-          &format!("Unknown ingredient \"{ingredient}\""),
-          iter::once("<New Ingredient>")
-            .chain(matching_ingredients.iter().map(|i| i.name.as_str()))
-            .collect(),
-        )
-        .raw_prompt()?
-        .index
-      };
+      let existing_ingredient = selected_ingredient
+        .checked_sub(1)
+        .map(|index| &matching_ingredients[index].0);
 
       self.ingredients.push(IngredientEntry {
-        ingredient_id: (selected_ingredient > 0).then(|| matching_ingredients[selected_ingredient - 1].id),
-        name: ingredient.to_string(),
-        quantity: quantity.to_string(),
-        notes_markdown: notes.to_string(),
+        ingredient_id: existing_ingredient.map(|i| i.id),
+        name: existing_ingredient.map(|i| i.name.clone()).unwrap_or(line.name),
+        quantity: line.quantity,
+        notes_markdown: line.notes_markdown,
+        category_id: None,
       });
     }
 
     Ok(())
   }
 
-  fn get_duplicate_ingredients<'a>(&'a self) -> BTreeSet<&'a str> {
+  fn check_duplicate_ingredients(&self) -> Result<(), String> {
+    let duplicate_ingredients = self.get_duplicate_ingredients();
+    if duplicate_ingredients.is_empty() {
+      Ok(())
+    } else {
+      Err(format!(
+        "Duplicate ingredients: {}",
+        duplicate_ingredients.iter().join(", ")
+      ))
+    }
+  }
+
+  fn get_duplicate_ingredients(&self) -> BTreeSet<&str> {
     let mut all_ingredients = BTreeSet::new();
     let mut duplicate_ingredients = BTreeSet::new();
 
@@ -221,10 +655,23 @@ impl RecipeEditor {
       "".into()
     };
 
+    let tags = if !self.selected_tags.is_empty() {
+      format!(
+        "\n**Tags:** {}",
+        self
+          .selected_tags
+          .iter()
+          .map(|index| &self.all_tags[*index].name)
+          .join(", ")
+      )
+    } else {
+      "".into()
+    };
+
     let skin = MadSkin::default();
     skin.print_text(&format!(
-      "# {}\n---\n## Ingredients\n{}\n\n## Instructions\n{}\n{}",
-      self.name, ingredients_str, self.instructions_markdown, notes,
+      "# {}\n---\n## Ingredients\n{}\n\n## Instructions\n{}\n{}{}",
+      self.name, ingredients_str, self.instructions_markdown, notes, tags,
     ));
   }
 
@@ -238,6 +685,8 @@ impl RecipeEditor {
       }
       .insert_recipe(conn)?;
 
+      // Rolled back along with the recipe if saving fails, so the draft is still there
+      self.discard_draft(conn)?;
       self.handle_ingredients_and_tags(&recipe, conn)?;
 
       Ok(recipe)
@@ -247,14 +696,19 @@ impl RecipeEditor {
   /// Update an existing recipe in the database
   fn update_recipe(self, conn: &mut SqliteConnection) -> QueryResult<()> {
     conn.transaction(|conn| {
+      // Keep a copy of the old recipe in case the changes need to be reverted
+      let old_recipe = Recipe::find(&self.existing_recipe_id.unwrap(), conn)?;
+      RecipeRevision::create_from_recipe(&old_recipe, conn)?;
+
       let recipe = Recipe {
-        id: self.existing_recipe_id.unwrap(),
         name: self.name.clone(),
         instructions_markdown: self.instructions_markdown.clone(),
         notes_markdown: self.notes_markdown.clone(),
+        ..old_recipe
       }
       .update(conn)?;
 
+      self.discard_draft(conn)?;
       self.handle_ingredients_and_tags(&recipe, conn)?;
 
       Ok(())
@@ -264,7 +718,11 @@ impl RecipeEditor {
   fn handle_ingredients_and_tags(mut self, recipe: &Recipe, conn: &mut SqliteConnection) -> QueryResult<()> {
     // Create ingredients that don't exist
     for ingredient in self.ingredients.iter_mut().filter(|i| i.ingredient_id.is_none()) {
-      let new_ingredient = IngredientConstructor { name: &ingredient.name }.insert_ingredient(conn)?;
+      let new_ingredient = IngredientConstructor {
+        name: &ingredient.name,
+        category_id: ingredient.category_id.as_deref(),
+      }
+      .insert_ingredient(conn)?;
 
       // Set the created IDs
       ingredient.ingredient_id = Some(new_ingredient.id);
@@ -291,10 +749,56 @@ impl RecipeEditor {
     // Set the tags
     recipe.set_tags_ids(self.selected_tags.iter().map(|index| &self.all_tags[*index].id), conn)?;
 
+    // Steps refer to the ingredients, so they are read last
+    recipe.update_steps(conn)?;
+
     Ok(())
   }
 }
 
+impl EditorSection {
+  const ALL: [EditorSection; 5] = [
+    EditorSection::Name,
+    EditorSection::Ingredients,
+    EditorSection::Instructions,
+    EditorSection::Notes,
+    EditorSection::Tags,
+  ];
+}
+
+impl fmt::Display for MenuOption {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      MenuOption::Save => write!(f, "Save changes"),
+      MenuOption::Edit(EditorSection::Name) => write!(f, "Edit name"),
+      MenuOption::Edit(EditorSection::Ingredients) => write!(f, "Edit ingredients"),
+      MenuOption::Edit(EditorSection::Instructions) => write!(f, "Edit instructions"),
+      MenuOption::Edit(EditorSection::Notes) => write!(f, "Edit notes"),
+      MenuOption::Edit(EditorSection::Tags) => write!(f, "Edit tags"),
+      MenuOption::Cancel => write!(f, "Cancel"),
+    }
+  }
+}
+
+impl fmt::Display for DraftOption {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      DraftOption::New => write!(f, "<Start a new recipe>"),
+      DraftOption::Resume(draft) if draft.name.is_empty() => write!(f, "<Unnamed> (saved {})", draft.updated_at),
+      DraftOption::Resume(draft) => write!(f, "{} (saved {})", draft.name, draft.updated_at),
+    }
+  }
+}
+
+impl fmt::Display for CategoryOption<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self.0 {
+      None => write!(f, "<None>"),
+      Some(category) => write!(f, "{}", category.name),
+    }
+  }
+}
+
 impl IngredientEntry {
   pub fn markdown_string(&self) -> String {
     let quantity = self.quantity.trim();
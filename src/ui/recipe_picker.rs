@@ -0,0 +1,109 @@
+use diesel::SqliteConnection;
+use inquire::Select;
+use itertools::Itertools;
+use std::fmt;
+use std::io::{stdin, IsTerminal};
+
+use crate::models::{Recipe, Tag};
+use crate::subcommand;
+
+/// Recipe as shown in the selection prompt
+struct RecipeOption {
+  recipe: Recipe,
+  tags: Vec<Tag>,
+}
+
+impl RecipeOption {
+  fn load(recipe: Recipe, conn: &mut SqliteConnection) -> subcommand::Result<Self> {
+    let tags = recipe.get_tags_ordered(conn)?;
+    Ok(Self { recipe, tags })
+  }
+}
+
+impl fmt::Display for RecipeOption {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{} (ID: {})", self.recipe.name, self.recipe.id)?;
+    if !self.tags.is_empty() {
+      write!(f, " [{}]", self.tags.iter().map(|t| &t.name).join(", "))?;
+    }
+    Ok(())
+  }
+}
+
+/// Look up a recipe by ID, name, slug, or prefix
+///
+/// Asks the user to pick one if the reference is ambiguous, or from all recipes if no reference is given.
+/// Returns None if nothing matched, the recipe is in the trash, or the user skipped the prompt.
+pub fn pick_recipe(reference: Option<&str>, conn: &mut SqliteConnection) -> subcommand::Result<Option<Recipe>> {
+  let reference = match reference {
+    Some(reference) => reference,
+    None if !stdin().is_terminal() => return Err("No recipe specified".into()),
+    None => {
+      let all_recipes = Recipe::all_ordered(conn)?;
+      if all_recipes.is_empty() {
+        println!("No recipes in the database");
+        return Ok(None);
+      }
+
+      return select_recipe("Recipe:", all_recipes, conn);
+    },
+  };
+
+  let mut candidates = Recipe::find_by_reference(reference, conn)?;
+  match candidates.len() {
+    0 => {
+      println!("No such recipe: {}", reference);
+      Ok(None)
+    },
+
+    1 => match candidates.pop() {
+      Some(recipe) if recipe.is_deleted() => {
+        println!("Recipe is in the trash: {} (ID: {})", recipe.name, recipe.id);
+        Ok(None)
+      },
+      recipe => Ok(recipe),
+    },
+
+    _ if !stdin().is_terminal() => Err(
+      format!(
+        "\"{}\" matches multiple recipes:\n{}",
+        reference,
+        candidates
+          .into_iter()
+          .map(|r| format!("  {} (ID: {})", r.name, r.id))
+          .join("\n")
+      )
+      .into(),
+    ),
+
+    _ => select_recipe(&format!("Multiple recipes match \"{}\":", reference), candidates, conn),
+  }
+}
+
+fn select_recipe(
+  message: &str,
+  recipes: Vec<Recipe>,
+  conn: &mut SqliteConnection,
+) -> subcommand::Result<Option<Recipe>> {
+  let options = recipes
+    .into_iter()
+    .map(|r| RecipeOption::load(r, conn))
+    .collect::<subcommand::Result<Vec<_>>>()?;
+
+  Ok(
+    Select::new(message, options)
+      .with_filter(&fuzzy_filter)
+      .prompt_skippable()?
+      .map(|option| option.recipe),
+  )
+}
+
+/// Filter for inquire prompts that matches if all input characters appear in order
+pub fn fuzzy_filter<T>(input: &str, _: &T, value: &str, _: usize) -> bool {
+  let mut value_chars = value.chars().flat_map(char::to_lowercase);
+  input
+    .chars()
+    .filter(|c| !c.is_whitespace())
+    .flat_map(char::to_lowercase)
+    .all(|c| value_chars.any(|v| v == c))
+}
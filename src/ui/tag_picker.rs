@@ -0,0 +1,49 @@
+use diesel::SqliteConnection;
+use inquire::Select;
+use std::fmt;
+use std::io::{stdin, IsTerminal};
+
+use super::fuzzy_filter;
+use crate::models::Tag;
+use crate::subcommand;
+
+/// Tag as shown in the selection prompt
+struct TagOption(Tag);
+
+impl fmt::Display for TagOption {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{} ({})", self.0.name, self.0.id)
+  }
+}
+
+/// Look up a tag by ID, or ask the user to pick one if no ID is given
+///
+/// Returns None if the tag doesn't exist or the user skipped the prompt.
+pub fn pick_tag(id: Option<&str>, conn: &mut SqliteConnection) -> subcommand::Result<Option<Tag>> {
+  match id {
+    Some(id) => {
+      let tag = Tag::find_optional(&id.to_string(), conn)?;
+      if tag.is_none() {
+        println!("No such tag: {}", id);
+      }
+      Ok(tag)
+    },
+
+    None if !stdin().is_terminal() => Err("No tag specified".into()),
+
+    None => {
+      let all_tags = Tag::all_ordered(conn)?;
+      if all_tags.is_empty() {
+        println!("No tags in the database");
+        return Ok(None);
+      }
+
+      Ok(
+        Select::new("Tag:", all_tags.into_iter().map(TagOption).collect())
+          .with_filter(&fuzzy_filter)
+          .prompt_skippable()?
+          .map(|option| option.0),
+      )
+    },
+  }
+}
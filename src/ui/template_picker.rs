@@ -0,0 +1,49 @@
+use diesel::SqliteConnection;
+use inquire::Select;
+use std::fmt;
+use std::io::{stdin, IsTerminal};
+
+use super::fuzzy_filter;
+use crate::models::RecipeTemplate;
+use crate::subcommand;
+
+/// Template as shown in the selection prompt
+struct TemplateOption(RecipeTemplate);
+
+impl fmt::Display for TemplateOption {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{} ({})", self.0.name, self.0.id)
+  }
+}
+
+/// Look up a recipe template by ID, or ask the user to pick one if no ID is given
+///
+/// Returns None if the template doesn't exist or the user skipped the prompt.
+pub fn pick_template(id: Option<&str>, conn: &mut SqliteConnection) -> subcommand::Result<Option<RecipeTemplate>> {
+  match id {
+    Some(id) => {
+      let template = RecipeTemplate::find_optional(&id.to_string(), conn)?;
+      if template.is_none() {
+        println!("No such template: {}", id);
+      }
+      Ok(template)
+    },
+
+    None if !stdin().is_terminal() => Err("No template specified".into()),
+
+    None => {
+      let all_templates = RecipeTemplate::all_ordered(conn)?;
+      if all_templates.is_empty() {
+        println!("No recipe templates in the database");
+        return Ok(None);
+      }
+
+      Ok(
+        Select::new("Template:", all_templates.into_iter().map(TemplateOption).collect())
+          .with_filter(&fuzzy_filter)
+          .prompt_skippable()?
+          .map(|option| option.0),
+      )
+    },
+  }
+}
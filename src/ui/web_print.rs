@@ -0,0 +1,122 @@
+use diesel::SqliteConnection;
+use horrorshow::{helper::doctype, html, Raw};
+use itertools::Itertools;
+use markdown::Options;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use tempfile::Builder;
+
+use crate::links::{LinkIndex, LinkStyle};
+use crate::models::{Recipe, SubstitutionPlan};
+use crate::subcommand;
+
+/// Render the recipe to a temporary HTML file and open it in the browser for printing
+///
+/// Recipes linked from this one get their own pages in the same folder, so the links work.
+///   Links on those pages to recipes further away are written as plain names.
+pub fn open_print_page(
+  recipe: &Recipe,
+  substitutions: &SubstitutionPlan,
+  conn: &mut SqliteConnection,
+) -> subcommand::Result<()> {
+  let all_links = LinkIndex::load(LinkStyle::Html, conn)?;
+  let connected = recipe.get_connected_recipes(&all_links, conn)?;
+  let links = all_links.with_linkable_ids(connected.iter().map(|r| r.id).chain([recipe.id]).collect());
+
+  let folder = Builder::new().prefix("recipe-").tempdir()?.into_path();
+  let path = folder.join(recipe.get_html_filename());
+  write_recipe_page(recipe, substitutions, true, &links, &path, conn)?;
+
+  for linked in connected {
+    let linked_path = folder.join(linked.get_html_filename());
+    write_recipe_page(&linked, &SubstitutionPlan::default(), false, &links, &linked_path, conn)?;
+  }
+
+  open::that(path)?;
+
+  Ok(())
+}
+
+fn write_recipe_page(
+  recipe: &Recipe,
+  substitutions: &SubstitutionPlan,
+  print: bool,
+  links: &LinkIndex,
+  path: &Path,
+  conn: &mut SqliteConnection,
+) -> subcommand::Result<()> {
+  let raw_body = markdown::to_html_with_options(
+    &recipe.markdown_string_with_substitutions(substitutions, links, conn)?,
+    &Options::gfm(),
+  )?;
+  let nutrition = recipe.get_nutrition(conn)?;
+  let mut file = File::create(path)?;
+  write!(
+    file,
+    "{}",
+    html! {
+      : doctype::HTML;
+      html {
+        head {
+            title : &recipe.name;
+            style: Raw(include_str!("styles.css"));
+            @ if print {
+              script: "window.print();";
+            }
+        }
+        body(class = "markdown") {
+          : Raw(&raw_body);
+          @ if !nutrition.is_empty() {
+            div(class = "nutrition-label") {
+              h2 : "Nutrition Facts";
+              p(class = "nutrition-servings") : "Whole recipe";
+              table {
+                @ for (label, amount) in nutrition.total.rows() {
+                  tr {
+                    th : label;
+                    td : amount;
+                  }
+                }
+              }
+              @ if !nutrition.gaps.is_empty() {
+                p(class = "nutrition-gaps") {
+                  : format!("Not counted: {}", nutrition.gaps.iter().map(|gap| &gap.ingredient).join(", "));
+                }
+              }
+            }
+          }
+        }
+      }
+    },
+  )?;
+
+  Ok(())
+}
+
+/// Render any markdown, like a cooking schedule, to a temporary HTML file and open it for printing
+pub fn open_markdown_page(title: &str, markdown: &str) -> subcommand::Result<()> {
+  let raw_body = markdown::to_html_with_options(markdown, &Options::gfm())?;
+  let (mut file, path) = Builder::new().suffix(".html").tempfile()?.keep()?;
+  write!(
+    file,
+    "{}",
+    html! {
+      : doctype::HTML;
+      html {
+        head {
+            title : title;
+            style: Raw(include_str!("styles.css"));
+            script: "window.print();";
+        }
+        body(class = "markdown") {
+          : Raw(&raw_body);
+        }
+      }
+    },
+  )?;
+
+  open::that(path)?;
+
+  Ok(())
+}